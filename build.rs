@@ -0,0 +1,87 @@
+//! Generates the registry of solutions that the main binary runs in-process for `all` and `time`.
+//! Every `src/bin/YYYY-DD.rs` is included as a module of the main binary, see `src/main.rs`.
+//! Also exposes the version of the compiler as `AOC_RUSTC_VERSION` and the enabled target features as
+//! `AOC_TARGET_FEATURES` for benchmark metadata.
+use std::{env, fmt::Write, fs, path::Path, process::Command};
+
+fn main() {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let rustc_version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map_or_else(
+            || "unknown".into(),
+            |output| String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        );
+    println!("cargo::rustc-env=AOC_RUSTC_VERSION={rustc_version}");
+
+    let target_features = env::var("CARGO_CFG_TARGET_FEATURE").unwrap_or_default();
+    println!("cargo::rustc-env=AOC_TARGET_FEATURES={target_features}");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+    println!("cargo::rerun-if-changed={}", bin_dir.display());
+
+    let mut days: Vec<(u16, u8, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter_map(|path| {
+                    let stem = path.file_stem()?.to_str()?;
+                    let is_rust = path.extension()? == "rs";
+                    let (year, day) = stem.split_once('-')?;
+                    let year = year.parse::<u16>().ok().filter(|y| *y >= 2015)?;
+                    let day = day.parse::<u8>().ok().filter(|d| (1..=25).contains(d))?;
+                    (is_rust && stem.len() == 7).then(|| (year, day, path.display().to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    days.sort_unstable();
+
+    let mut out = String::new();
+
+    for (year, day, path) in &days {
+        writeln!(out, "#[path = {path:?}]").unwrap();
+        writeln!(out, "#[allow(dead_code)]").unwrap();
+        writeln!(out, "#[cfg(not(any(test, feature = \"dhat-heap\")))]").unwrap();
+        writeln!(out, "mod day_{year}_{day:02};").unwrap();
+        // the year of the file name has to match the year passed to `solution!`.
+        writeln!(out, "#[cfg(not(any(test, feature = \"dhat-heap\")))]").unwrap();
+        writeln!(
+            out,
+            "const _: () = assert!(day_{year}_{day:02}::YEAR.into_inner() == {year}, \"solution!() in {path} is expected to pass `year = {year}`\");"
+        )
+        .unwrap();
+    }
+
+    writeln!(out).unwrap();
+    writeln!(out, "#[cfg(not(any(test, feature = \"dhat-heap\")))]").unwrap();
+    writeln!(
+        out,
+        "pub const SOLUTIONS: &[advent_of_code::template::runner::Solution] = &["
+    )
+    .unwrap();
+    for (year, day, _) in &days {
+        writeln!(
+            out,
+            "    advent_of_code::template::runner::Solution {{ year: advent_of_code::year!({year}), day: advent_of_code::day!({day}), run: day_{year}_{day:02}::run_parts }},"
+        )
+        .unwrap();
+    }
+    writeln!(out, "];").unwrap();
+
+    writeln!(out).unwrap();
+    writeln!(out, "#[cfg(any(test, feature = \"dhat-heap\"))]").unwrap();
+    writeln!(
+        out,
+        "pub const SOLUTIONS: &[advent_of_code::template::runner::Solution] = &[];"
+    )
+    .unwrap();
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, out).unwrap();
+}
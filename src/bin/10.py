@@ -87,8 +87,8 @@ def actions_to_matrix(actions: list[int], n: int):
 
 
 if __name__ == "__main__":
-    example_file = Path(__file__).parent.parent.parent / "data/examples/10.txt"
-    input_file = Path(__file__).parent.parent.parent / "data/inputs/10.txt"
+    example_file = Path(__file__).parent.parent.parent / "data/2025/examples/10.txt"
+    input_file = Path(__file__).parent.parent.parent / "data/2025/inputs/10.txt"
     actions, joltages = parse_input(example_file)
     checksum = 0
     for action, jolts in zip(actions, joltages):
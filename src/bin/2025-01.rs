@@ -1,14 +1,17 @@
-advent_of_code::solution!(1);
+advent_of_code::solution!(1, year = 2025);
+use advent_of_code::parse::{ParseError, parse};
 
-fn parse_input(input: &str) -> Vec<(char, i32)> {
+fn parse_input(input: &str) -> Result<Vec<(char, i32)>, ParseError> {
     let mut out = Vec::new();
     for s in input.lines() {
-        let mut chars = s.chars();
-        let direction = chars.next().unwrap();
-        let steps = chars.collect::<String>().parse::<i32>().unwrap();
+        let direction = match s.chars().next() {
+            Some(direction @ ('L' | 'R')) => direction,
+            _ => return Err(ParseError::new(input, s, "expected a rotation like `L68`")),
+        };
+        let steps = parse::<i32>(input, &s[1..])?;
         out.push((direction, steps));
     }
-    out
+    Ok(out)
 }
 
 fn _process_move(pos: i32, instruction: &(char, i32)) -> (i32, u64) {
@@ -58,8 +61,8 @@ fn process_move_mod(pos: i32, instruction: &(char, i32)) -> (i32, u64) {
     (new_pos, clicks.into())
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let moves = parse_input(input);
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let moves = parse_input(input)?;
     let mut pos = 50;
     let mut out = 0;
     for m in moves.iter() {
@@ -68,11 +71,11 @@ pub fn part_one(input: &str) -> Option<u64> {
             out += 1
         }
     }
-    Some(out)
+    Ok(out)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let moves = parse_input(input);
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let moves = parse_input(input)?;
     let mut pos = 50;
     let mut out = 0;
     let mut clicks;
@@ -80,7 +83,7 @@ pub fn part_two(input: &str) -> Option<u64> {
         (pos, clicks) = process_move_mod(pos, m);
         out += clicks;
     }
-    Some(out)
+    Ok(out)
 }
 
 #[cfg(test)]
@@ -89,13 +92,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
+        assert_eq!(result, Ok(3));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
+        assert_eq!(result, Ok(6));
     }
 }
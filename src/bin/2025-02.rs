@@ -1,4 +1,4 @@
-advent_of_code::solution!(2);
+advent_of_code::solution!(2, year = 2025);
 use std::cmp::{max, min};
 use std::collections::HashSet;
 use std::iter;
@@ -93,7 +93,7 @@ fn sum_candidates(lo_raw: String, hi_raw: String) -> u64 {
     let hi = hi_raw.parse::<u64>().unwrap();
     for i in 1..(len_lo / 2) + 1 {
         // unless mod corresponds, cannot have exact repeats
-        if len_lo % i != 0 {
+        if !len_lo.is_multiple_of(i) {
             continue;
         }
         // possible candidate values; take first i
@@ -126,13 +126,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(1227775554));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(4174379265));
     }
 }
@@ -1,20 +1,25 @@
-advent_of_code::solution!(3);
+advent_of_code::solution!(3, year = 2025);
+use advent_of_code::parse::ParseError;
 use std::cmp::max;
 
-fn parse_input(input: &str) -> Vec<Vec<u64>> {
+fn parse_input(input: &str) -> Result<Vec<Vec<u64>>, ParseError> {
     let mut out = Vec::new();
     for line in input.lines() {
         let row = line
-            .chars()
-            .map(|d| d.to_digit(10).unwrap() as u64)
-            .collect();
+            .char_indices()
+            .map(|(i, d)| {
+                d.to_digit(10).map(u64::from).ok_or_else(|| {
+                    ParseError::new(input, &line[i..i + d.len_utf8()], "expected a digit")
+                })
+            })
+            .collect::<Result<_, _>>()?;
         out.push(row);
     }
-    out
+    Ok(out)
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let banks = parse_input(input);
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let banks = parse_input(input)?;
     let mut out = 0;
     let n = banks[0].len();
     for bank in banks.iter() {
@@ -36,11 +41,11 @@ pub fn part_one(input: &str) -> Option<u64> {
         }
         out += best;
     }
-    Some(out)
+    Ok(out)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let banks = parse_input(input);
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let banks = parse_input(input)?;
     let mut out = 0;
     let n = banks[0].len();
     for bank in banks.iter() {
@@ -65,7 +70,7 @@ pub fn part_two(input: &str) -> Option<u64> {
         let best_val = best.iter().fold(0, |acc, elem| acc * 10 + elem);
         out += best_val;
     }
-    Some(out)
+    Ok(out)
 }
 
 #[cfg(test)]
@@ -74,13 +79,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(357));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
+        assert_eq!(result, Ok(357));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3121910778619));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
+        assert_eq!(result, Ok(3121910778619));
     }
 }
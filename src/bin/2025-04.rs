@@ -1,4 +1,4 @@
-advent_of_code::solution!(4);
+advent_of_code::solution!(4, year = 2025);
 use advent_of_code::get_neighboring_indices_2d;
 
 fn parse_input(input: &str) -> Vec<Vec<char>> {
@@ -72,13 +72,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(13));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(43));
     }
 }
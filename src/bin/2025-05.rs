@@ -1,4 +1,4 @@
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, year = 2025);
 use regex::Regex;
 use std::cmp::{max, min};
 
@@ -98,13 +98,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(3));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(14));
     }
 }
@@ -1,6 +1,6 @@
 use std::iter::zip;
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(6, year = 2025);
 
 #[derive(Debug, PartialEq)]
 enum Operator {
@@ -108,13 +108,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(4277556));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(3263827));
     }
 }
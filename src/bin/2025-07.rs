@@ -1,4 +1,4 @@
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, year = 2025);
 // use advent_of_code::prettyprint_grid;
 use std::cmp::max;
 
@@ -83,13 +83,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(21));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(40));
     }
 }
@@ -1,7 +1,7 @@
-advent_of_code::solution!(8);
+advent_of_code::solution!(8, year = 2025, parse = parse_input);
 
 #[derive(Debug, Clone, Copy)]
-struct Point {
+pub struct Point {
     x: i64,
     y: i64,
     z: i64,
@@ -81,9 +81,8 @@ fn parse_input(input: &str) -> Vec<Point> {
     out
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let junctions = parse_input(input);
-    let connections = get_sorted_connections(&junctions);
+pub fn part_one(junctions: &[Point]) -> Option<u64> {
+    let connections = get_sorted_connections(junctions);
     let mut network = DisjointNetwork::new(junctions.len());
     // Uncomment below for actual solution; test uses 10
     //for &((a, b), _) in connections.iter().take(1000) {
@@ -98,9 +97,8 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(sizes[0] * sizes[1] * sizes[2])
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let junctions = parse_input(input);
-    let connections = get_sorted_connections(&junctions);
+pub fn part_two(junctions: &[Point]) -> Option<u64> {
+    let connections = get_sorted_connections(junctions);
     let mut network = DisjointNetwork::new(junctions.len());
     for &((a, b), _) in connections.iter() {
         network.union(a, b);
@@ -117,13 +115,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_input(&advent_of_code::template::read_file(
+            "examples", YEAR, DAY,
+        )));
         assert_eq!(result, Some(40));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse_input(&advent_of_code::template::read_file(
+            "examples", YEAR, DAY,
+        )));
         assert_eq!(result, Some(25272));
     }
 }
@@ -1,7 +1,7 @@
 use itertools::Itertools;
 use std::cmp::{max, min};
 
-advent_of_code::solution!(9);
+advent_of_code::solution!(9, year = 2025);
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 struct Point {
@@ -140,7 +140,7 @@ pub fn part_two(input: &str) -> Option<u64> {
             rectangles.push((area, (*p1, *p2)));
         }
     }
-    rectangles.sort_unstable_by(|a, b| b.0.cmp(&a.0));
+    rectangles.sort_unstable_by_key(|r| std::cmp::Reverse(r.0));
     'outer: for &(a, (p1, p2)) in rectangles.iter() {
         let (ymin, ymax) = if p1.y < p2.y {
             (p1.y, p2.y)
@@ -192,13 +192,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(50));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(24));
     }
 }
@@ -2,7 +2,7 @@ use core::fmt;
 use itertools::Itertools;
 use std::collections::{HashSet, VecDeque};
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(10, year = 2025);
 
 type Action = Vec<usize>;
 
@@ -536,13 +536,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(12));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(463));
     }
 }
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(11, year = 2025);
 
 fn parse_input(input: &str) -> HashMap<String, Vec<String>> {
     let mut out = HashMap::new();
@@ -69,13 +69,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(2));
     }
 }
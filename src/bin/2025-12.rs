@@ -3,7 +3,7 @@ use std::collections::{HashMap, HashSet};
 use advent_of_code::get_neighboring_indices_2d;
 use regex::Regex;
 
-advent_of_code::solution!(12);
+advent_of_code::solution!(12, year = 2025);
 
 #[derive(Debug, Clone)]
 struct Shape {
@@ -98,7 +98,6 @@ impl Region {
                     inner(grid, h, w, area, visited, ni, nj);
                 }
             }
-            return;
         }
         let mut area = 0;
         let mut visited = HashSet::new();
@@ -116,7 +115,9 @@ impl Region {
 }
 
 type Chargrid = Vec<Vec<char>>;
-fn parse_input(input: &str) -> (Vec<(i8, Chargrid)>, Vec<(usize, usize)>, Vec<Vec<u8>>) {
+type ParsedInput = (Vec<(i8, Chargrid)>, Vec<(usize, usize)>, Vec<Vec<u8>>);
+
+fn parse_input(input: &str) -> ParsedInput {
     let mut shapes = Vec::new();
     let mut gridsizes = Vec::new();
     let mut requirements = Vec::new();
@@ -208,17 +209,13 @@ fn generate_shapes(mut chars: Chargrid, id: i8) -> Vec<Shape> {
     shapes
 }
 
-fn solve(
-    gridsize: (usize, usize),
-    requirements: &Vec<u8>,
-    shapes: &HashMap<i8, Vec<Shape>>,
-) -> bool {
-    let mut reqs = requirements.clone();
+fn solve(gridsize: (usize, usize), requirements: &[u8], shapes: &HashMap<i8, Vec<Shape>>) -> bool {
+    let mut reqs = requirements.to_vec();
     let mut grid = Region::new(gridsize.0, gridsize.1);
 
     fn next_hole(grid: &Region, curr_i: usize, curr_j: usize) -> Option<(isize, isize)> {
         // find next de Bruijn hole
-        let (mut hi, mut hj) = (curr_i as usize, curr_j as usize);
+        let (mut hi, mut hj) = (curr_i, curr_j);
         // base case at the start
         if (hi == 0) && (hj == 0) {
             return Some((hi as isize, hj as isize));
@@ -239,7 +236,7 @@ fn solve(
         None
     }
 
-    fn remaining_area(requirements: &Vec<u8>, shapes: &HashMap<i8, Vec<Shape>>) -> u8 {
+    fn remaining_area(requirements: &[u8], shapes: &HashMap<i8, Vec<Shape>>) -> u8 {
         // simple flood fill from bottom right corner (de Bruijn starts from top left so should work)
         let mut out = 0;
         for (i, &req) in requirements.iter().enumerate() {
@@ -278,7 +275,7 @@ fn solve(
                     if grid.place_shape(hi, hj, shape.clone()) {
                         reqs[*shape_id as usize] -= 1;
                         // remaining area heuristic
-                        if remaining_area(&reqs, &shapes) > grid.usable_area() {
+                        if remaining_area(reqs, shapes) > grid.usable_area() {
                             grid.remove_shape(hi as usize, hj as usize);
                             reqs[*shape_id as usize] += 1;
                         // recursive call
@@ -318,7 +315,7 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(solvable)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two(_input: &str) -> Option<u64> {
     None
 }
 
@@ -328,17 +325,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let example = std::fs::read_to_string(
-            "/home/lauri/Documents/misc/advent-of-code2025/data/examples/12.txt",
-        )
-        .unwrap();
-        let result = part_one(example.as_str());
-        assert_eq!(result, None);
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
+        assert_eq!(result, Some(2));
     }
 
     //    #[test]
     //    fn test_part_two() {
-    //        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+    //        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
     //        assert_eq!(result, None);
     //    }
 }
@@ -1,3 +1,4 @@
+pub mod parse;
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
@@ -38,7 +39,7 @@ pub fn get_neighboring_indices_2d(
         .collect()
 }
 
-/// Prettyprint a grid of characters
+/// Prettyprint a grid of characters. Use [`template::ocr`] to read letters drawn on it instead.
 pub fn prettyprint_grid(grid: &[Vec<char>]) -> String {
     grid.iter()
         .map(|row| row.iter().collect::<String>())
@@ -1,4 +1,6 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, read, scaffold, solve, time, verify, watch,
+};
 use args::{AppArguments, parse};
 
 #[cfg(feature = "today")]
@@ -6,74 +8,350 @@ use advent_of_code::template::Day;
 #[cfg(feature = "today")]
 use std::process;
 
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        Day, Year,
+        benchmark_report::ReportFormat,
+        commands::{scaffold::DEFAULT_TEMPLATE, time},
+        config::{self, Config},
+        input::InputSource,
+        runner::{BenchConfig, RunOptions},
+        selection::DaySelection,
+        timeouts::DayTimeouts,
+    };
+    use std::{ffi::OsString, process, time::Duration};
 
     pub enum AppArguments {
         Download {
+            year: Year,
             day: Day,
         },
         Read {
+            year: Year,
+            day: Day,
+        },
+        Examples {
+            year: Year,
             day: Day,
+            dry_run: bool,
+            overwrite: bool,
         },
         Scaffold {
+            year: Year,
             day: Day,
             download: bool,
             overwrite: bool,
+            template: String,
         },
         Solve {
+            year: Year,
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            part_timeout: Option<Duration>,
+            input: InputSource,
+        },
+        WatchSolve {
+            year: Year,
+            day: Day,
+            release: bool,
         },
         All {
+            year: Year,
+            selection: DaySelection,
             release: bool,
+            isolated: Option<DayTimeouts>,
+            part_timeout: Option<Duration>,
+            jobs: usize,
         },
         Time {
-            all: bool,
+            year: Year,
+            selection: DaySelection,
+            isolated: Option<DayTimeouts>,
+            options: RunOptions,
+            outputs: time::Outputs,
+        },
+        Verify {
+            year: Year,
             day: Option<Day>,
-            store: bool,
+            isolated: Option<DayTimeouts>,
+            part_timeout: Option<Duration>,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+    /// Days are only valid if they are part of the event of the selected year.
+    fn check_day(year: Year, day: Day) -> Result<Day, String> {
+        if year.contains(day) {
+            Ok(day)
+        } else {
+            Err(format!(
+                "expecting a day number between 1 and {} for {year}",
+                year.last_day().into_inner()
+            ))
+        }
+    }
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
+    /// Parts that run longer than `--part-timeout` seconds are reported as timed out.
+    fn part_timeout(args: &mut pico_args::Arguments) -> Result<Option<Duration>, String> {
+        let error = || "expecting --part-timeout to be a positive number of seconds".to_string();
+        match args.opt_value_from_str::<_, f64>("--part-timeout") {
+            Ok(None) => Ok(None),
+            Ok(Some(secs)) => Duration::try_from_secs_f64(secs)
+                .ok()
+                .filter(|timeout| !timeout.is_zero())
+                .map(Some)
+                .ok_or_else(error),
+            Err(_) => Err(error()),
+        }
+    }
+
+    /// A running part cannot be stopped in-process, so `--part-timeout` requires a child process per day.
+    fn isolated_part_timeout(
+        args: &mut pico_args::Arguments,
+        isolated: Option<&DayTimeouts>,
+    ) -> Result<Option<Duration>, String> {
+        match part_timeout(args)? {
+            Some(_) if isolated.is_none() => Err("--part-timeout requires --isolated".into()),
+            timeout => Ok(timeout),
+        }
+    }
+
+    /// `--isolated` runs every day in a child process, which is killed once it exceeds its `--timeout`.
+    /// The timeout of a day in `aoc.toml` takes precedence over the default of `--timeout`, but not over `--timeout <day>=<secs>`.
+    /// With the `dhat-heap` feature no solutions are compiled into this binary, see `build.rs`, so days always run isolated.
+    fn isolation(
+        args: &mut pico_args::Arguments,
+        config: &Config,
+        year: Year,
+    ) -> Result<Option<DayTimeouts>, Box<dyn std::error::Error>> {
+        let isolated = args.contains("--isolated") || cfg!(feature = "dhat-heap");
+        let timeouts: Option<DayTimeouts> = args.opt_value_from_str("--timeout")?;
+
+        match (isolated, timeouts) {
+            (true, timeouts) => {
+                let configured = config
+                    .days
+                    .iter()
+                    .filter(|((y, _), _)| *y == year)
+                    .filter_map(|((_, day), day_config)| Some((*day, day_config.timeout?)));
+                Ok(Some(
+                    timeouts.unwrap_or_default().with_fallbacks(configured),
+                ))
+            }
+            (false, None) => Ok(None),
+            (false, Some(_)) => Err("--timeout requires --isolated".into()),
+        }
+    }
+
+    /// `--example` takes an optional example number, which `pico_args` has no notion of.
+    /// The number has to be passed as `--example=N`, so that `cargo solve --example 01` still reads `01` as the day.
+    /// Removes the flag from `args`, returning `Some` if the flag was passed.
+    fn take_example(args: &mut Vec<OsString>) -> Result<Option<Option<u8>>, String> {
+        let Some(index) = args.iter().position(|x| {
+            x == "--example" || x.to_str().is_some_and(|x| x.starts_with("--example="))
+        }) else {
+            return Ok(None);
+        };
+
+        let flag = args.remove(index);
+        match flag.to_str().and_then(|x| x.strip_prefix("--example=")) {
+            None => Ok(Some(None)),
+            Some(number) => match number.parse() {
+                Ok(number) if number > 0 => Ok(Some(Some(number))),
+                _ => Err(format!(
+                    "expecting --example=N with a positive example number, found \"{number}\""
+                )),
             },
+        }
+    }
+
+    /// Without `--input <path>`, `--example[=N]` or `--stdin`, a solution runs against the puzzle input.
+    fn input_source(
+        args: &mut pico_args::Arguments,
+        example: Option<Option<u8>>,
+    ) -> Result<InputSource, Box<dyn std::error::Error>> {
+        let mut sources: Vec<InputSource> = vec![];
+
+        if let Some(path) = args.opt_value_from_str("--input")? {
+            sources.push(InputSource::File(path));
+        }
+        if let Some(number) = example {
+            sources.push(InputSource::Example(number));
+        }
+        if args.contains("--stdin") {
+            sources.push(InputSource::Stdin);
+        }
+
+        match sources.len() {
+            0 => Ok(InputSource::Puzzle),
+            1 => Ok(sources.remove(0)),
+            _ => Err("expecting only one of --input, --example and --stdin".into()),
+        }
+    }
+
+    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+        let mut raw_args: Vec<OsString> = std::env::args_os().skip(1).collect();
+        let mut example = take_example(&mut raw_args)?;
+        let mut args = pico_args::Arguments::from_vec(raw_args);
+
+        let config = config::load()?;
+        let subcommand = args.subcommand()?;
+        // `aoc.toml` takes precedence over `AOC_YEAR`, which is set in `.cargo/config.toml`.
+        let year = match (args.opt_value_from_str("--year")?, config.year) {
+            (Some(year), _) | (None, Some(year)) => year,
+            (None, None) => Year::from_env()?,
+        };
+
+        let app_args = match subcommand.as_deref() {
+            Some("all") => {
+                let isolated = isolation(&mut args, config, year)?;
+                let part_timeout = isolated_part_timeout(&mut args, isolated.as_ref())?;
+
+                // days only run concurrently in child processes, so that their output can be buffered.
+                // their bins are built one after another before that, as cargo locks the build directory anyway.
+                let jobs = args.opt_value_from_str("--jobs")?.unwrap_or(1);
+                if jobs == 0 || (jobs > 1 && isolated.is_none()) {
+                    return Err(
+                        "expecting --jobs to be at least 1, more jobs require --isolated".into(),
+                    );
+                }
+
+                AppArguments::All {
+                    year,
+                    release: args.contains("--release"),
+                    isolated,
+                    part_timeout,
+                    jobs,
+                    selection: args.opt_free_from_str()?.unwrap_or_else(DaySelection::all),
+                }
+            }
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let isolated = isolation(&mut args, config, year)?;
+
+                let defaults = config.bench;
+                let bench = BenchConfig {
+                    warmup: args
+                        .opt_value_from_str("--warmup")?
+                        .map_or(defaults.warmup, Duration::from_millis),
+                    budget: args
+                        .opt_value_from_str("--budget")?
+                        .map_or(defaults.budget, Duration::from_millis),
+                    min_samples: args
+                        .opt_value_from_str("--min-samples")?
+                        .unwrap_or(defaults.min_samples),
+                    max_samples: args
+                        .opt_value_from_str("--max-samples")?
+                        .unwrap_or(defaults.max_samples),
+                };
+
+                if bench.min_samples == 0 || bench.min_samples > bench.max_samples {
+                    return Err("expecting 0 < --min-samples <= --max-samples".into());
+                }
+
+                let options = RunOptions {
+                    bench: Some(bench),
+                    part_timeout: isolated_part_timeout(&mut args, isolated.as_ref())?,
+                    ..RunOptions::default()
+                };
+
+                // regressions beyond `--threshold` percent make `--compare` fail.
+                let threshold: Option<f64> = args.opt_value_from_str("--threshold")?;
+                let compare_threshold = match (args.contains("--compare"), threshold) {
+                    (true, threshold) => Some(threshold.unwrap_or(10.0)),
+                    (false, None) => None,
+                    (false, Some(_)) => return Err("--threshold requires --compare".into()),
+                };
+
+                // `--report <format>` exports the timings of the year, see `benchmark_report`.
+                let format: Option<ReportFormat> = args.opt_value_from_str("--report")?;
+                let report = match (format, args.opt_value_from_str("--report-file")?) {
+                    (Some(format), path) => Some((format, path)),
+                    (None, None) => None,
+                    (None, Some(_)) => return Err("--report-file requires --report".into()),
+                };
+
+                // without a selection, days that are fully benched are skipped.
+                let selection = if all {
+                    DaySelection::all()
+                } else {
+                    args.opt_free_from_str()?
+                        .unwrap_or_else(DaySelection::unsolved)
+                };
 
                 AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
-                    store,
+                    year,
+                    selection,
+                    isolated,
+                    options,
+                    outputs: time::Outputs {
+                        store,
+                        compare_threshold,
+                        report,
+                    },
                 }
             }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                year,
+                day: check_day(year, args.free_from_str()?)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                year,
+                day: check_day(year, args.free_from_str()?)?,
+            },
+            Some("examples") => AppArguments::Examples {
+                year,
+                dry_run: args.contains("--dry-run"),
+                overwrite: args.contains("--overwrite"),
+                day: check_day(year, args.free_from_str()?)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                year,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                template: args
+                    .opt_value_from_str("--template")?
+                    .unwrap_or_else(|| DEFAULT_TEMPLATE.into()),
+                day: check_day(year, args.free_from_str()?)?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+            Some("solve") => {
+                // NOTE: options are parsed first, so that their values are not taken for the day.
+                let input = input_source(&mut args, example.take())?;
+                AppArguments::Solve {
+                    year,
+                    release: args.contains("--release"),
+                    submit: args.opt_value_from_str("--submit")?,
+                    dhat: args.contains("--dhat"),
+                    part_timeout: part_timeout(&mut args)?,
+                    input,
+                    day: check_day(year, args.free_from_str()?)?,
+                }
+            }
+            Some("watch-solve") => AppArguments::WatchSolve {
+                year,
                 release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+                day: check_day(year, args.free_from_str()?)?,
             },
+            Some("verify") => {
+                let isolated = isolation(&mut args, config, year)?;
+                AppArguments::Verify {
+                    year,
+                    part_timeout: isolated_part_timeout(&mut args, isolated.as_ref())?,
+                    isolated,
+                    day: args
+                        .opt_free_from_str()?
+                        .map(|day| check_day(year, day))
+                        .transpose()?,
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -86,7 +364,10 @@ mod args {
             }
         };
 
-        let remaining = args.finish();
+        let mut remaining = args.finish();
+        if example.is_some() {
+            remaining.push("--example".into());
+        }
         if !remaining.is_empty() {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
@@ -102,38 +383,96 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::All {
+                year,
+                selection,
+                release,
+                isolated,
+                part_timeout,
+                jobs,
+            } => {
+                all::handle(
+                    solutions::SOLUTIONS,
+                    year,
+                    &selection,
+                    release,
+                    isolated.as_ref(),
+                    part_timeout,
+                    jobs,
+                );
+            }
+            AppArguments::Time {
+                year,
+                selection,
+                isolated,
+                options,
+                outputs,
+            } => time::handle(
+                solutions::SOLUTIONS,
+                year,
+                &selection,
+                isolated.as_ref(),
+                options,
+                &outputs,
+            ),
+            AppArguments::Download { year, day } => download::handle(year, day),
+            AppArguments::Read { year, day } => read::handle(year, day),
+            AppArguments::Examples {
+                year,
+                day,
+                dry_run,
+                overwrite,
+            } => examples::handle(year, day, dry_run, overwrite),
             AppArguments::Scaffold {
+                year,
                 day,
                 download,
                 overwrite,
+                template,
             } => {
-                scaffold::handle(day, overwrite);
+                // the puzzle is downloaded first, so that its title can be filled into the template.
                 if download {
-                    download::handle(day);
+                    download::handle(year, day);
                 }
+                scaffold::handle(year, day, overwrite, &template);
             }
             AppArguments::Solve {
+                year,
                 day,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                part_timeout,
+                input,
+            } => solve::handle(year, day, release, dhat, submit, part_timeout, &input),
+            AppArguments::WatchSolve { year, day, release } => watch::handle(year, day, release),
+            AppArguments::Verify {
+                year,
+                day,
+                isolated,
+                part_timeout,
+            } => {
+                verify::handle(
+                    solutions::SOLUTIONS,
+                    year,
+                    day,
+                    isolated.as_ref(),
+                    part_timeout,
+                );
+            }
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                    Some((year, day)) => {
+                        download::handle(year, day);
+                        scaffold::handle(year, day, false, scaffold::DEFAULT_TEMPLATE);
+                        examples::handle(year, day, false, false);
+                        read::handle(year, day)
                     }
                     None => {
                         eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 12th of december. Please use `scaffold` with a specific day."
+                            "`today` command can only be run during advent. \
+                            Please use `scaffold` with a specific day."
                         );
                         process::exit(1)
                     }
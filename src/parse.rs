@@ -0,0 +1,116 @@
+/// Module with an error type for malformed puzzle inputs.
+/// Parts can return `Result<T, ParseError>`, a failed part then prints where in the input it failed:
+///
+/// ```text
+/// line 2, column 2: could not parse `3x0`: invalid digit found in string
+///   |
+/// 2 | R3x0
+///   |  ^^^
+/// ```
+use std::{error::Error, fmt::Display, str::FromStr};
+
+/// Describes which part of the input could not be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// Starting at 1.
+    pub line: usize,
+    /// Starting at 1, counted in characters.
+    pub column: usize,
+    /// The line of the input the error occurred in.
+    pub snippet: String,
+    /// Length of the part of the line the error is about, in characters.
+    pub len: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    /// An error about `span`, which is expected to be a slice of `input`, e.g. a line or a field of a line.
+    /// A `span` that is not part of `input` is looked up by its contents instead.
+    pub fn new(input: &str, span: &str, message: impl Display) -> Self {
+        let start = (span.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+        let is_slice = start
+            .checked_add(span.len())
+            .is_some_and(|end| end <= input.len());
+        let offset = if is_slice && input.is_char_boundary(start) {
+            start
+        } else {
+            input.find(span).unwrap_or(0)
+        };
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        let snippet = input[line_start..line_end].trim_end_matches('\r');
+
+        ParseError {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet: snippet.to_string(),
+            len: span.lines().next().unwrap_or("").chars().count(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.snippet)?;
+        write!(
+            f,
+            "{gutter} | {}{}",
+            " ".repeat(self.column - 1),
+            "^".repeat(self.len.max(1))
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parse `span`, a slice of `input`, reporting where it is located in `input` if that fails.
+pub fn parse<T>(input: &str, span: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    span.parse()
+        .map_err(|e| ParseError::new(input, span, format!("could not parse `{span}`: {e}")))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ParseError, parse};
+
+    const INPUT: &str = "L68\nR3x0\nL5\n";
+
+    #[test]
+    fn locates_spans() {
+        let line = INPUT.lines().nth(1).unwrap();
+        let error = parse::<i32>(INPUT, &line[1..]).unwrap_err();
+        assert_eq!((error.line, error.column, error.len), (2, 2, 3));
+        assert_eq!(error.snippet, "R3x0");
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: could not parse `3x0`: invalid digit found in string
+  |
+2 | R3x0
+  |  ^^^"
+        );
+    }
+
+    #[test]
+    fn finds_copied_spans() {
+        let error = ParseError::new(INPUT, "L5", "unexpected move");
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(parse::<i32>(INPUT, &INPUT[1..3]), Ok(68));
+    }
+}
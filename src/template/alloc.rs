@@ -0,0 +1,213 @@
+/// Module with a global allocator that counts the heap usage of solution parts.
+/// Enabled by the `count-alloc` feature, e.g. `cargo run --release --features count-alloc -- time --store`.
+/// The peak heap size and number of allocations of the first run of each part are then reported and stored with its timing.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    collections::HashMap,
+    fmt::Display,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use tinyjson::JsonValue;
+
+#[cfg(all(feature = "count-alloc", feature = "dhat-heap"))]
+compile_error!("the features `count-alloc` and `dhat-heap` both replace the global allocator");
+
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+static CURRENT_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Wraps the system allocator, counting allocations and tracking the peak number of allocated bytes.
+/// NOTE: the counters are shared by all threads, parts run one at a time so they are attributed to the running part.
+pub struct CountingAlloc;
+
+fn record_growth(bytes: usize) {
+    let current = CURRENT_BYTES.fetch_add(bytes, Ordering::Relaxed) + bytes;
+    PEAK_BYTES.fetch_max(current, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            record_growth(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            record_growth(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        CURRENT_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            if new_size >= layout.size() {
+                record_growth(new_size - layout.size());
+            } else {
+                CURRENT_BYTES.fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+            }
+        }
+        new_ptr
+    }
+}
+
+/// Heap usage of a single run of a part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Memory {
+    /// Most bytes allocated at once during the run, on top of what was allocated before.
+    pub peak_bytes: usize,
+    /// Number of allocations and reallocations.
+    pub allocations: usize,
+}
+
+impl Memory {
+    #[allow(clippy::cast_precision_loss)]
+    pub fn format_bytes(&self) -> String {
+        let bytes = self.peak_bytes as f64;
+        match self.peak_bytes {
+            0..1024 => format!("{} B", self.peak_bytes),
+            1024..1_048_576 => format!("{:.1} KiB", bytes / 1024.0),
+            1_048_576..1_073_741_824 => format!("{:.1} MiB", bytes / 1_048_576.0),
+            _ => format!("{:.1} GiB", bytes / 1_073_741_824.0),
+        }
+    }
+}
+
+impl Display for Memory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let plural = if self.allocations == 1 { "" } else { "s" };
+        write!(
+            f,
+            "{} peak, {} allocation{plural}",
+            self.format_bytes(),
+            self.allocations
+        )
+    }
+}
+
+/// Run `func`, counting its heap usage. `None` unless the `count-alloc` feature is enabled.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<Memory>) {
+    let (result, memory) = count(func);
+    (result, cfg!(feature = "count-alloc").then_some(memory))
+}
+
+fn count<T>(func: impl FnOnce() -> T) -> (T, Memory) {
+    let baseline = CURRENT_BYTES.load(Ordering::Relaxed);
+    PEAK_BYTES.store(baseline, Ordering::Relaxed);
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+
+    let result = func();
+
+    let memory = Memory {
+        peak_bytes: PEAK_BYTES.load(Ordering::Relaxed).saturating_sub(baseline),
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+    };
+    (result, memory)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Memory> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Memory) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Memory {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|v| v as usize)
+                .ok_or(format!("Expected memory.{key} to be a number."))
+        };
+
+        Ok(Memory {
+            peak_bytes: number("peak_bytes")?,
+            allocations: number("allocations")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::alloc::{GlobalAlloc, Layout};
+
+    use tinyjson::JsonValue;
+
+    use super::{CountingAlloc, Memory, count, measure};
+
+    #[test]
+    fn formats_memory() {
+        let memory = |peak_bytes, allocations| Memory {
+            peak_bytes,
+            allocations,
+        };
+        assert_eq!(memory(512, 1).to_string(), "512 B peak, 1 allocation");
+        assert_eq!(memory(1536, 3).to_string(), "1.5 KiB peak, 3 allocations");
+        assert_eq!(memory(3 * 1_048_576, 0).format_bytes(), "3.0 MiB");
+        assert_eq!(memory(1_073_741_824, 0).format_bytes(), "1.0 GiB");
+    }
+
+    #[test]
+    fn counts_allocations() {
+        // NOTE: the allocator is only installed with `count-alloc`, so it is called directly here.
+        let (_, memory) = count(|| unsafe {
+            let layout = Layout::from_size_align(1024, 8).unwrap();
+            let ptr = CountingAlloc.alloc(layout);
+            let ptr = CountingAlloc.realloc(ptr, layout, 4096);
+            CountingAlloc.dealloc(ptr, Layout::from_size_align(4096, 8).unwrap());
+        });
+        assert_eq!(memory.allocations >= 2, true);
+        assert_eq!(memory.peak_bytes >= 4096, true);
+        assert_eq!(measure(|| ()).1.is_some(), cfg!(feature = "count-alloc"));
+    }
+
+    #[test]
+    fn converts_json() {
+        let memory = Memory {
+            peak_bytes: 2048,
+            allocations: 12,
+        };
+        assert_eq!(Memory::try_from(&JsonValue::from(&memory)), Ok(memory));
+        assert_eq!(Memory::try_from(&JsonValue::Null).is_err(), true);
+    }
+}
@@ -0,0 +1,267 @@
+/// Module that normalizes the results of solution parts into answers.
+/// Parts return `Option<T>` or `Result<T, E>` for any `T` that implements [`ToAnswer`], e.g. integers, strings or an [`Answer`] itself.
+/// Answers with more than one line are grids, e.g. letters drawn as ASCII art, which are read with [`ocr`].
+use std::fmt::Display;
+
+use crate::template::ocr;
+
+/// The normalized result of a part.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(i128),
+    /// A single line of text, without surrounding whitespace.
+    /// Also holds unsigned integers that do not fit into an `i128`.
+    Text(String),
+    /// Rows of a drawing, without trailing whitespace.
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// Normalize an answer that was printed or stored as text, see [`ToAnswer`] for `str`.
+    pub fn parse(s: &str) -> Self {
+        let rows: Vec<&str> = s.lines().filter(|row| !row.trim().is_empty()).collect();
+
+        if rows.len() > 1 {
+            return Answer::grid(s.lines());
+        }
+
+        let s = rows.first().map_or("", |row| row.trim());
+        match s.parse::<i128>() {
+            Ok(value) => Answer::Integer(value),
+            Err(_) => Answer::Text(s.to_string()),
+        }
+    }
+
+    /// An answer drawn over multiple rows, leading and trailing blank rows are dropped.
+    pub fn grid<S: AsRef<str>>(rows: impl IntoIterator<Item = S>) -> Self {
+        let rows: Vec<String> = rows
+            .into_iter()
+            .map(|row| row.as_ref().trim_end().to_string())
+            .skip_while(String::is_empty)
+            .collect();
+
+        let len = rows.len() - rows.iter().rev().take_while(|row| row.is_empty()).count();
+        Answer::Grid(rows[..len].to_vec())
+    }
+
+    /// The form of the answer the website accepts, `None` for grids that do not draw known letters.
+    pub fn submittable(&self) -> Option<String> {
+        match self {
+            Answer::Integer(value) => Some(value.to_string()),
+            Answer::Text(text) if !text.is_empty() => Some(text.clone()),
+            Answer::Text(_) => None,
+            Answer::Grid(rows) => {
+                let grid: Vec<Vec<char>> = rows.iter().map(|row| row.chars().collect()).collect();
+                ocr::recognize_chars(&grid)
+            }
+        }
+    }
+
+    /// The answer as it is reported and stored: the submittable form if there is one, the drawing otherwise.
+    pub fn text(&self) -> String {
+        self.submittable().unwrap_or_else(|| self.to_string())
+    }
+
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Grid(rows) if rows.len() > 1)
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{value}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+/// Converts the result of a part into an [`Answer`].
+/// Implement this for custom result types, e.g. by returning [`Answer::grid`] for a drawing.
+pub trait ToAnswer {
+    fn to_answer(&self) -> Answer;
+}
+
+impl ToAnswer for Answer {
+    fn to_answer(&self) -> Answer {
+        self.clone()
+    }
+}
+
+macro_rules! impl_to_answer_for_integers {
+    ($($ty:ty),*) => {
+        $(
+            impl ToAnswer for $ty {
+                fn to_answer(&self) -> Answer {
+                    Answer::Integer(i128::from(*self))
+                }
+            }
+        )*
+    };
+}
+
+impl_to_answer_for_integers!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl ToAnswer for u128 {
+    fn to_answer(&self) -> Answer {
+        i128::try_from(*self).map_or_else(|_| Answer::Text(self.to_string()), Answer::Integer)
+    }
+}
+
+impl ToAnswer for usize {
+    fn to_answer(&self) -> Answer {
+        (*self as u128).to_answer()
+    }
+}
+
+impl ToAnswer for isize {
+    fn to_answer(&self) -> Answer {
+        Answer::Integer(*self as i128)
+    }
+}
+
+impl ToAnswer for char {
+    fn to_answer(&self) -> Answer {
+        Answer::parse(&self.to_string())
+    }
+}
+
+impl ToAnswer for str {
+    fn to_answer(&self) -> Answer {
+        Answer::parse(self)
+    }
+}
+
+impl ToAnswer for String {
+    fn to_answer(&self) -> Answer {
+        Answer::parse(self)
+    }
+}
+
+impl ToAnswer for [Vec<char>] {
+    fn to_answer(&self) -> Answer {
+        Answer::grid(self.iter().map(|row| row.iter().collect::<String>()))
+    }
+}
+
+impl ToAnswer for Vec<Vec<char>> {
+    fn to_answer(&self) -> Answer {
+        self.as_slice().to_answer()
+    }
+}
+
+impl ToAnswer for [Vec<bool>] {
+    fn to_answer(&self) -> Answer {
+        Answer::grid(self.iter().map(|row| {
+            row.iter()
+                .map(|&lit| if lit { '#' } else { '.' })
+                .collect::<String>()
+        }))
+    }
+}
+
+impl ToAnswer for Vec<Vec<bool>> {
+    fn to_answer(&self) -> Answer {
+        self.as_slice().to_answer()
+    }
+}
+
+impl<T: ToAnswer + ?Sized> ToAnswer for &T {
+    fn to_answer(&self) -> Answer {
+        (**self).to_answer()
+    }
+}
+
+/// Converts what a part returns into its answer, or the message of the error the part failed with.
+/// `None` means that the part is not solved yet.
+pub trait PartResult {
+    fn to_outcome(&self) -> Result<Option<Answer>, String>;
+}
+
+impl<T: ToAnswer> PartResult for Option<T> {
+    fn to_outcome(&self) -> Result<Option<Answer>, String> {
+        Ok(self.as_ref().map(ToAnswer::to_answer))
+    }
+}
+
+/// Errors are described by their `Display`, e.g. a [`ParseError`](crate::parse::ParseError) with its location.
+impl<T: ToAnswer, E: Display> PartResult for Result<T, E> {
+    fn to_outcome(&self) -> Result<Option<Answer>, String> {
+        match self {
+            Ok(result) => Ok(Some(result.to_answer())),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answer, PartResult, ToAnswer};
+
+    #[test]
+    fn normalizes_numbers() {
+        assert_eq!(42_u64.to_answer(), Answer::Integer(42));
+        assert_eq!((-7_i32).to_answer(), Answer::Integer(-7));
+        assert_eq!(" 42\n".to_answer(), Answer::Integer(42));
+        assert_eq!(u128::MAX.to_answer(), Answer::Text(u128::MAX.to_string()));
+        assert_eq!(
+            u128::MAX.to_answer().submittable(),
+            Some(u128::MAX.to_string())
+        );
+    }
+
+    #[test]
+    fn normalizes_text() {
+        assert_eq!("abc,def \n".to_answer(), Answer::Text("abc,def".into()));
+        assert_eq!('x'.to_answer().submittable(), Some("x".into()));
+        assert_eq!("".to_answer().submittable(), None);
+    }
+
+    #[test]
+    fn normalizes_grids() {
+        let answer = "\n#..# \n#..#\n\n".to_string().to_answer();
+        assert_eq!(answer, Answer::Grid(vec!["#..#".into(), "#..#".into()]));
+        assert_eq!(answer.is_multiline(), true);
+        assert_eq!(answer.submittable(), None);
+        assert_eq!(answer.text(), "#..#\n#..#");
+        assert_eq!(Answer::parse(&answer.to_string()), answer);
+        assert_eq!(Answer::grid(["", "  "]), Answer::Grid(vec![]));
+        assert_eq!("\n 42 \n\n".to_answer(), Answer::Integer(42));
+    }
+
+    #[test]
+    fn converts_part_results() {
+        assert_eq!(Some(3_u64).to_outcome(), Ok(Some(Answer::Integer(3))));
+        assert_eq!(None::<u64>.to_outcome(), Ok(None));
+        assert_eq!(
+            Ok::<_, String>("abc").to_outcome(),
+            Ok(Some(Answer::Text("abc".into())))
+        );
+        assert_eq!(
+            Err::<u64, _>("bad input").to_outcome(),
+            Err("bad input".into())
+        );
+    }
+
+    #[test]
+    fn reads_letter_grids() {
+        let grid: Vec<Vec<bool>> = [
+            ".##..###.",
+            "#..#.#..#",
+            "#..#.###.",
+            "####.#..#",
+            "#..#.#..#",
+            "#..#.###.",
+        ]
+        .iter()
+        .map(|row| row.chars().map(|c| c == '#').collect())
+        .collect();
+        let answer = grid.to_answer();
+        assert_eq!(answer.is_multiline(), true);
+        assert_eq!(answer.submittable(), Some("AB".into()));
+        assert_eq!(answer.text(), "AB");
+    }
+}
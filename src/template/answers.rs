@@ -0,0 +1,48 @@
+/// Module that stores the accepted answers for the real puzzle inputs.
+/// Each answer lives in its own file, e.g. `data/2025/answers/01-2.txt` for the second part of day 1 in 2025.
+use std::{fs, io, path::PathBuf};
+
+use crate::template::{Day, Year, answer::Answer, config, data_dir};
+
+fn get_answer_path(year: Year, day: Day, part: u8) -> PathBuf {
+    data_dir(year)
+        .join("answers")
+        .join(format!("{day}-{part}.txt"))
+}
+
+/// Read the stored answer for a part, falling back to the expected answer in `aoc.toml`.
+/// `None` if no answer has been stored or configured yet.
+pub fn read(year: Year, day: Day, part: u8) -> Option<String> {
+    fs::read_to_string(get_answer_path(year, day, part))
+        .ok()
+        .map(|answer| answer.trim_end().to_string())
+        .or_else(|| config::get().day(year, day).answer(part).map(String::from))
+}
+
+/// Store the answer for a part, overwriting a previously stored answer.
+pub fn store(year: Year, day: Day, part: u8, answer: &Answer) -> Result<(), io::Error> {
+    let path = get_answer_path(year, day, part);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, format!("{}\n", answer.text()))
+}
+
+/// Whether a computed answer matches a stored answer once both are normalized, see [`Answer::parse`].
+pub fn matches(stored: &str, answer: &str) -> bool {
+    Answer::parse(stored) == Answer::parse(answer)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::matches;
+
+    #[test]
+    fn normalizes_answers() {
+        assert_eq!(matches("123\n", "123"), true);
+        assert_eq!(matches("#..#\n.##.\n", "#..#\n.##."), true);
+        assert_eq!(matches("123", "1234"), false);
+        assert_eq!(matches(" 123", "123"), true);
+        assert_eq!(matches("abc", "ABC"), false);
+    }
+}
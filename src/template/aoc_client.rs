@@ -0,0 +1,562 @@
+/// Built-in client for the Advent of Code website.
+/// Downloads inputs and puzzle descriptions and submits answers using the session cookie of a logged-in user.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use ureq::Agent;
+
+use crate::template::{Day, Year};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/speug/advent-of-code2025 by advent_of_code template";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// No session cookie was found in the environment or the session files.
+    MissingSession,
+    Http(String),
+    BadStatus {
+        status: u16,
+        body: String,
+    },
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set ADVENT_OF_CODE_SESSION or write it to ~/.adventofcode.session."
+            ),
+            AocClientError::Http(e) => write!(f, "request failed: {e}"),
+            AocClientError::BadStatus { status, body } => {
+                write!(f, "server responded with status {status}: {}", body.trim())
+            }
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        AocClientError::Http(e.to_string())
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+/// The verdict of the website for a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    /// The answer is wrong and the website did not say in which direction.
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// The part has already been solved, the answer was not checked.
+    AlreadySolved,
+    /// An answer was submitted too recently, the answer was not checked.
+    RateLimited {
+        wait: Duration,
+    },
+}
+
+impl Display for SubmissionOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionOutcome::Correct => write!(f, "That's the right answer!"),
+            SubmissionOutcome::Incorrect => write!(f, "That's not the right answer."),
+            SubmissionOutcome::TooHigh => write!(f, "That's not the right answer, it is too high."),
+            SubmissionOutcome::TooLow => write!(f, "That's not the right answer, it is too low."),
+            SubmissionOutcome::AlreadySolved => {
+                write!(f, "This part has already been solved.")
+            }
+            SubmissionOutcome::RateLimited { wait } => {
+                write!(f, "You gave an answer too recently, wait {wait:?}.")
+            }
+        }
+    }
+}
+
+pub struct AocClient {
+    agent: Agent,
+    base_url: String,
+    session: String,
+    year: Year,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, year: Year) -> Self {
+        let agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .user_agent(USER_AGENT)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .new_agent();
+
+        AocClient {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            year,
+        }
+    }
+
+    /// Create a client for the puzzles of `year` from the environment:
+    ///  1. the session cookie is read from `ADVENT_OF_CODE_SESSION`, `~/.adventofcode.session` or `~/.config/adventofcode.session`.
+    ///  2. the base URL can be overridden with `AOC_BASE_URL`.
+    pub fn from_env(year: Year) -> Result<Self, AocClientError> {
+        let session = get_session().ok_or(AocClientError::MissingSession)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(AocClient::new(&base_url, &session, year))
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        let mut response = self
+            .agent
+            .get(url)
+            .header("Cookie", &format!("session={}", self.session))
+            .call()?;
+        let status = response.status().as_u16();
+        let body = response.body_mut().read_to_string()?;
+
+        if status == 200 {
+            Ok(body)
+        } else {
+            Err(AocClientError::BadStatus { status, body })
+        }
+    }
+
+    /// Fetch the puzzle input of a day.
+    pub fn input(&self, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", self.day_url(day)))
+    }
+
+    /// Fetch the puzzle description of a day, converted to markdown.
+    /// Contains the second part once the first part is solved.
+    pub fn puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let html = self.get(&self.day_url(day))?;
+        Ok(html::puzzle_to_markdown(&html))
+    }
+
+    /// Submit an answer for a part and return the verdict of the website.
+    pub fn submit(
+        &self,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmissionOutcome, AocClientError> {
+        let level = part.to_string();
+        let mut response = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(day)))
+            .header("Cookie", &format!("session={}", self.session))
+            .send_form([("level", level.as_str()), ("answer", answer)])?;
+        let status = response.status().as_u16();
+        let body = response.body_mut().read_to_string()?;
+
+        if status != 200 {
+            return Err(AocClientError::BadStatus { status, body });
+        }
+
+        parse_submission(&body).ok_or(AocClientError::BadStatus {
+            status,
+            body: "unrecognized response to submission.".into(),
+        })
+    }
+}
+
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("ADVENT_OF_CODE_SESSION")
+        && !session.trim().is_empty()
+    {
+        return Some(session.trim().to_string());
+    }
+
+    let home = PathBuf::from(env::var("HOME").ok()?);
+    let config_dir =
+        env::var("XDG_CONFIG_HOME").map_or_else(|_| home.join(".config"), PathBuf::from);
+
+    [
+        home.join(".adventofcode.session"),
+        config_dir.join("adventofcode.session"),
+    ]
+    .iter()
+    .find_map(|path| fs::read_to_string(path).ok())
+    .map(|session| session.trim().to_string())
+    .filter(|session| !session.is_empty())
+}
+
+/// Parse the verdict from the HTML response of a submission.
+fn parse_submission(html: &str) -> Option<SubmissionOutcome> {
+    let text = html::strip_tags(html::article(html).unwrap_or(html));
+
+    if text.contains("That's the right answer") {
+        Some(SubmissionOutcome::Correct)
+    } else if text.contains("You don't seem to be solving the right level") {
+        Some(SubmissionOutcome::AlreadySolved)
+    } else if text.contains("You gave an answer too recently") {
+        Some(SubmissionOutcome::RateLimited {
+            wait: parse_wait(&text).unwrap_or(Duration::from_secs(60)),
+        })
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            Some(SubmissionOutcome::TooHigh)
+        } else if text.contains("your answer is too low") {
+            Some(SubmissionOutcome::TooLow)
+        } else {
+            Some(SubmissionOutcome::Incorrect)
+        }
+    } else {
+        None
+    }
+}
+
+/// Parse the remaining wait time from e.g. `You have 1m 5s left to wait.`
+fn parse_wait(text: &str) -> Option<Duration> {
+    let (before, _) = text.split_once("left to wait")?;
+    let (_, amount) = before.rsplit_once("You have")?;
+
+    amount
+        .split_whitespace()
+        .map(|token| {
+            let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// Minimal conversion of the puzzle HTML to markdown.
+mod html {
+    /// The contents of the first `<article>` element.
+    pub fn article(html: &str) -> Option<&str> {
+        let start = html.find("<article")?;
+        let start = start + html[start..].find('>')? + 1;
+        let end = start + html[start..].find("</article>")?;
+        Some(&html[start..end])
+    }
+
+    /// Convert all `<article class="day-desc">` elements of a puzzle page to markdown.
+    pub fn puzzle_to_markdown(html: &str) -> String {
+        let mut articles = vec![];
+        let mut rest = html;
+
+        while let Some(start) = rest.find("<article class=\"day-desc\"") {
+            rest = &rest[start..];
+            let (Some(article), Some(end)) = (article(rest), rest.find("</article>")) else {
+                break;
+            };
+            articles.push(to_markdown(article));
+            rest = &rest[end..];
+        }
+
+        articles.join("\n\n") + "\n"
+    }
+
+    /// Remove all tags, keeping the decoded text.
+    pub fn strip_tags(html: &str) -> String {
+        tokens(html)
+            .filter_map(|token| match token {
+                Token::Text(text) => Some(decode_entities(text)),
+                Token::Tag(_) => None,
+            })
+            .collect()
+    }
+
+    enum Token<'a> {
+        Text(&'a str),
+        Tag(&'a str),
+    }
+
+    fn tokens(html: &str) -> impl Iterator<Item = Token<'_>> {
+        let mut rest = html;
+        std::iter::from_fn(move || {
+            if rest.is_empty() {
+                return None;
+            }
+            if let Some(stripped) = rest.strip_prefix('<')
+                && let Some(end) = stripped.find('>')
+            {
+                let tag = &stripped[..end];
+                rest = &stripped[end + 1..];
+                return Some(Token::Tag(tag));
+            }
+            // NOTE: text can start with a multi-byte character, e.g. `→`, so the search starts after the first char.
+            let first = rest.chars().next().map_or(1, char::len_utf8);
+            let end = rest[first..].find('<').map_or(rest.len(), |i| i + first);
+            let text = &rest[..end];
+            rest = &rest[end..];
+            Some(Token::Text(text))
+        })
+    }
+
+    fn tag_name(tag: &str) -> (bool, &str) {
+        let (is_closing, tag) = tag
+            .strip_prefix('/')
+            .map_or((false, tag), |tag| (true, tag));
+        let name = tag
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+        (is_closing, name)
+    }
+
+    fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+        let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+        let end = start + tag[start..].find('"')?;
+        Some(&tag[start..end])
+    }
+
+    fn to_markdown(html: &str) -> String {
+        let mut out = String::new();
+        let mut in_pre = false;
+        let mut links: Vec<String> = vec![];
+
+        for token in tokens(html) {
+            match token {
+                Token::Text(text) if in_pre => out.push_str(&decode_entities(text)),
+                Token::Text(text) => {
+                    let text = decode_entities(&text.replace('\n', " "));
+                    // NOTE: drop the indentation between block elements.
+                    if out.is_empty() || out.ends_with('\n') {
+                        out.push_str(text.trim_start());
+                    } else {
+                        out.push_str(&text);
+                    }
+                }
+                Token::Tag(tag) => match tag_name(tag) {
+                    (false, "h2") => out.push_str("## "),
+                    (true, "h2" | "p") => out.push_str("\n\n"),
+                    (false, "pre") => {
+                        in_pre = true;
+                        out.push_str("```\n");
+                    }
+                    (true, "pre") => {
+                        in_pre = false;
+                        if !out.ends_with('\n') {
+                            out.push('\n');
+                        }
+                        out.push_str("```\n\n");
+                    }
+                    (_, "code") if !in_pre => out.push('`'),
+                    (_, "em") => out.push('*'),
+                    (false, "li") => out.push_str("- "),
+                    (true, "li") => out.push('\n'),
+                    (true, "ul") => out.push('\n'),
+                    (false, "br") => out.push('\n'),
+                    (false, "a") => {
+                        links.push(attribute(tag, "href").unwrap_or_default().to_string());
+                        out.push('[');
+                    }
+                    (true, "a") => {
+                        out.push_str(&format!("]({})", links.pop().unwrap_or_default()));
+                    }
+                    _ => {}
+                },
+            }
+        }
+
+        // NOTE: collapse the blank lines that closing tags leave behind.
+        let mut collapsed = out.trim().to_string();
+        while collapsed.contains("\n\n\n") {
+            collapsed = collapsed.replace("\n\n\n", "\n\n");
+        }
+        collapsed
+    }
+
+    fn decode_entities(text: &str) -> String {
+        text.replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&#x27;", "'")
+            .replace("&apos;", "'")
+            .replace("&nbsp;", " ")
+            .replace("&amp;", "&")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+        time::Duration,
+    };
+
+    use super::{AocClient, SubmissionOutcome, html, parse_submission, parse_wait};
+    use crate::{day, year};
+
+    /// Serve a single request with `body` and return the base URL plus a handle yielding the raw request.
+    fn serve_once(body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some((name, value)) = line.split_once(':')
+                    && name.eq_ignore_ascii_case("content-length")
+                {
+                    content_length = value.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut payload = vec![0; content_length];
+            reader.read_exact(&mut payload).unwrap();
+            request.push_str(&String::from_utf8(payload).unwrap());
+
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn downloads_input() {
+        let (base_url, handle) = serve_once("1,2,3\n");
+        let client = AocClient::new(&base_url, "abc", year!(2025));
+        assert_eq!(client.input(day!(3)).unwrap(), "1,2,3\n");
+        let request = handle.join().unwrap();
+        assert_eq!(request.starts_with("GET /2025/day/3/input "), true);
+        assert_eq!(request.contains("session=abc"), true);
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, handle) = serve_once(
+            "<main><article><p>That's the right answer! You are one gold star closer.</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, "abc", year!(2025));
+        assert_eq!(
+            client.submit(day!(12), 2, "42").unwrap(),
+            SubmissionOutcome::Correct
+        );
+        let request = handle.join().unwrap();
+        assert_eq!(request.starts_with("POST /2025/day/12/answer "), true);
+        assert_eq!(request.ends_with("level=2&answer=42"), true);
+    }
+
+    #[test]
+    fn parses_submission_outcomes() {
+        let wrap = |s: &str| format!("<html><main><article><p>{s}</p></article></main></html>");
+        assert_eq!(
+            parse_submission(&wrap(
+                "That's not the right answer; your answer is too high. Please wait one minute."
+            )),
+            Some(SubmissionOutcome::TooHigh)
+        );
+        assert_eq!(
+            parse_submission(&wrap(
+                "That's not the right answer; your answer is too low."
+            )),
+            Some(SubmissionOutcome::TooLow)
+        );
+        assert_eq!(
+            parse_submission(&wrap("That's not the right answer. If you're stuck, ...")),
+            Some(SubmissionOutcome::Incorrect)
+        );
+        assert_eq!(
+            parse_submission(&wrap(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Some(SubmissionOutcome::AlreadySolved)
+        );
+        assert_eq!(
+            parse_submission(&wrap(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait."
+            )),
+            Some(SubmissionOutcome::RateLimited {
+                wait: Duration::from_secs(65)
+            })
+        );
+        assert_eq!(parse_submission(&wrap("Something else")), None);
+    }
+
+    #[test]
+    fn parses_wait_times() {
+        assert_eq!(
+            parse_wait("You have 35s left to wait."),
+            Some(Duration::from_secs(35))
+        );
+        assert_eq!(parse_wait("nothing here"), None);
+    }
+
+    #[test]
+    fn converts_puzzle_to_markdown() {
+        let page = r#"<main><article class="day-desc"><h2>--- Day 1: Test ---</h2><p>For example:</p>
+<pre><code>L68
+R&lt;3
+</code></pre>
+<p>The answer is <code><em>3</em></code>, see <a href="/2025/about">about</a>.</p>
+<ul><li>one</li><li>two</li></ul></article><p>Your puzzle answer was <code>1</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Now <em>count</em> more.</p></article></main>"#;
+
+        let expected = [
+            "## --- Day 1: Test ---",
+            "",
+            "For example:",
+            "",
+            "```",
+            "L68",
+            "R<3",
+            "```",
+            "",
+            "The answer is `*3*`, see [about](/2025/about).",
+            "",
+            "- one",
+            "- two",
+            "",
+            "## --- Part Two ---",
+            "",
+            "Now *count* more.",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(html::puzzle_to_markdown(page), expected);
+    }
+
+    #[test]
+    fn converts_non_ascii_text() {
+        let page = r#"<article class="day-desc"><h2>--- Day 2: Gift Shop ---</h2><p>Move <em>→</em> or <em>↓</em>…</p></article>"#;
+        assert_eq!(
+            html::puzzle_to_markdown(page),
+            "## --- Day 2: Gift Shop ---\n\nMove *→* or *↓*…\n"
+        );
+    }
+}
@@ -0,0 +1,537 @@
+/// Module that exports timings as reports, e.g. to publish benchmark pages or to plot them.
+/// `cargo time --report <format>` supports these formats:
+///  - `markdown`: a table like the one of the readme, columns can be picked, e.g. `markdown:day,part_1,part_2,total`.
+///  - `csv`: one row per timed part, with its statistics and memory usage.
+///  - `json`: the stored timings of the year.
+///  - `html`: a standalone page with a bar chart of the median time of every part on a log scale.
+use std::{error::Error, fmt::Display, path::PathBuf, str::FromStr, time::Duration};
+
+use tinyjson::JsonValue;
+
+use crate::template::alloc::Memory;
+use crate::template::readme_benchmarks::get_path_for_bin;
+use crate::template::stats::Stats;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Year, data_dir};
+
+/// A column of a markdown table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    Day,
+    Parse,
+    Part1,
+    Part2,
+    /// Sum of the median times of the day.
+    Total,
+    Memory1,
+    Memory2,
+}
+
+impl Column {
+    /// The columns of the readme table, see `readme_benchmarks`.
+    pub const DEFAULT: [Column; 4] = [Column::Day, Column::Parse, Column::Part1, Column::Part2];
+
+    fn title(self) -> &'static str {
+        match self {
+            Column::Day => "Day",
+            Column::Parse => "Parse",
+            Column::Part1 => "Part 1",
+            Column::Part2 => "Part 2",
+            Column::Total => "Total",
+            Column::Memory1 => "Memory 1",
+            Column::Memory2 => "Memory 2",
+        }
+    }
+
+    fn cell(self, year: Year, timing: &Timing) -> String {
+        // parts of a timed out day that have no time ran out of it.
+        let missing = if timing.timed_out { "timed out" } else { "-" };
+        let time = |time: &Option<String>| format!("`{}`", time.as_deref().unwrap_or(missing));
+
+        match self {
+            Column::Day => format!(
+                "[Day {}]({})",
+                timing.day.into_inner(),
+                get_path_for_bin(year, timing.day)
+            ),
+            Column::Parse => format!("`{}`", timing.parse.as_deref().unwrap_or("-")),
+            Column::Part1 => time(&timing.part_1),
+            Column::Part2 => time(&timing.part_2),
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Column::Total => format!("`{:.1?}`", Duration::from_nanos(timing.total_nanos as u64)),
+            Column::Memory1 => format_memory(timing.part_1_memory),
+            Column::Memory2 => format_memory(timing.part_2_memory),
+        }
+    }
+}
+
+fn format_memory(memory: Option<Memory>) -> String {
+    match memory {
+        Some(memory) => format!(
+            "`{}` ({} allocs)",
+            memory.format_bytes(),
+            memory.allocations
+        ),
+        None => "`-`".into(),
+    }
+}
+
+impl FromStr for Column {
+    type Err = ReportFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "day" => Ok(Column::Day),
+            "parse" => Ok(Column::Parse),
+            "part_1" => Ok(Column::Part1),
+            "part_2" => Ok(Column::Part2),
+            "total" => Ok(Column::Total),
+            "memory_1" => Ok(Column::Memory1),
+            "memory_2" => Ok(Column::Memory2),
+            _ => Err(ReportFormatFromStrError(s.to_string())),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown(Vec<Column>),
+    Csv,
+    Json,
+    Html,
+}
+
+impl ReportFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Markdown(_) => "md",
+            ReportFormat::Csv => "csv",
+            ReportFormat::Json => "json",
+            ReportFormat::Html => "html",
+        }
+    }
+
+    /// Where the report of a year is written to without `--report-file`, e.g. `data/2025/benchmarks.html`.
+    pub fn default_path(&self, year: Year) -> PathBuf {
+        data_dir(year).join(format!("benchmarks.{}", self.extension()))
+    }
+
+    /// Render the timings of a year.
+    pub fn render(&self, year: Year, timings: &Timings) -> String {
+        match self {
+            ReportFormat::Markdown(columns) => markdown(year, timings, columns),
+            ReportFormat::Csv => csv(timings),
+            ReportFormat::Json => json(year, timings),
+            ReportFormat::Html => html(year, timings),
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = ReportFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some(("markdown", columns)) => columns
+                .split(',')
+                .map(Column::from_str)
+                .collect::<Result<_, _>>()
+                .map(ReportFormat::Markdown),
+            None => match s {
+                "markdown" => Ok(ReportFormat::Markdown(Column::DEFAULT.to_vec())),
+                "csv" => Ok(ReportFormat::Csv),
+                "json" => Ok(ReportFormat::Json),
+                "html" => Ok(ReportFormat::Html),
+                _ => Err(ReportFormatFromStrError(s.to_string())),
+            },
+            Some(_) => Err(ReportFormatFromStrError(s.to_string())),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`ReportFormat`], contains the invalid term.
+#[derive(Debug)]
+pub struct ReportFormatFromStrError(String);
+
+impl Error for ReportFormatFromStrError {}
+
+impl Display for ReportFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid report format `{}`, expecting markdown, csv, json or html, markdown columns are picked from day, parse, part_1, part_2, total, memory_1 and memory_2, e.g. `markdown:day,part_1,part_2`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The header, alignment row and rows of a markdown table of timings.
+pub fn markdown_table(year: Year, data: &[Timing], columns: &[Column]) -> Vec<String> {
+    let titles: Vec<&str> = columns.iter().map(|column| column.title()).collect();
+    let mut lines = vec![
+        format!("| {} |", titles.join(" | ")),
+        format!("| {}  |", vec![":---:"; columns.len()].join(" | ")),
+    ];
+
+    for timing in data {
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|column| column.cell(year, timing))
+            .collect();
+        lines.push(format!("| {} |", cells.join(" | ")));
+    }
+
+    lines
+}
+
+fn markdown(year: Year, timings: &Timings, columns: &[Column]) -> String {
+    let mut lines = vec![format!("# Benchmarks ({year})"), String::new()];
+    lines.extend(markdown_table(year, &timings.data, columns));
+    lines.push(String::new());
+    lines.push(format!("**Total: {:.2}ms**", timings.total_millis()));
+
+    if let Some(environment) = &timings.environment {
+        lines.push(String::new());
+        lines.push(format!(
+            "<sub>Measured on {}.</sub>",
+            environment.describe()
+        ));
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// The timed parts of a day with their statistics, the parse step is labeled `parse`.
+fn timed_parts(timing: &Timing) -> impl Iterator<Item = (&'static str, &Stats, Option<Memory>)> {
+    [
+        ("parse", &timing.parse_stats, None),
+        ("1", &timing.part_1_stats, timing.part_1_memory),
+        ("2", &timing.part_2_stats, timing.part_2_memory),
+    ]
+    .into_iter()
+    .filter_map(|(part, stats, memory)| Some((part, stats.as_ref()?, memory)))
+}
+
+fn csv(timings: &Timings) -> String {
+    let mut lines = vec![
+        "day,part,median_nanos,mean_nanos,min_nanos,max_nanos,stddev_nanos,ci95_nanos,samples,outliers,peak_bytes,allocations".to_string(),
+    ];
+
+    for timing in &timings.data {
+        for (part, stats, memory) in timed_parts(timing) {
+            let (peak_bytes, allocations) = memory.map_or((String::new(), String::new()), |m| {
+                (m.peak_bytes.to_string(), m.allocations.to_string())
+            });
+            lines.push(format!(
+                "{},{part},{},{},{},{},{},{},{},{},{peak_bytes},{allocations}",
+                timing.day,
+                stats.median_nanos,
+                stats.mean_nanos,
+                stats.min_nanos,
+                stats.max_nanos,
+                stats.stddev_nanos,
+                stats.ci95_nanos,
+                stats.samples,
+                stats.outliers
+            ));
+        }
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn json(year: Year, timings: &Timings) -> String {
+    let mut json = JsonValue::from(timings.clone());
+    if let JsonValue::Object(map) = &mut json {
+        map.insert(
+            "year".into(),
+            JsonValue::Number(f64::from(year.into_inner())),
+        );
+    }
+    // NOTE: formatting only fails for non-finite numbers, which timings do not contain.
+    json.format().unwrap_or_default()
+}
+
+/* -------------------------------------------------------------------------- */
+
+const LABEL_WIDTH: f64 = 80.0;
+const CHART_WIDTH: f64 = 640.0;
+const VALUE_WIDTH: f64 = 80.0;
+const BAR_HEIGHT: f64 = 12.0;
+const ROW_HEIGHT: f64 = 16.0;
+const GROUP_GAP: f64 = 8.0;
+const AXIS_HEIGHT: f64 = 24.0;
+
+fn part_color(part: &str) -> &'static str {
+    match part {
+        "parse" => "#9e9e9e",
+        "1" => "#4e79a7",
+        _ => "#f28e2b",
+    }
+}
+
+fn part_label(part: &str) -> String {
+    match part {
+        "parse" => "Parse".into(),
+        part => format!("Part {part}"),
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A horizontal bar chart of the median time of every timed part, on a log scale.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn svg_chart(timings: &Timings) -> String {
+    let days: Vec<(&Timing, Vec<(&str, f64)>)> = timings
+        .data
+        .iter()
+        .map(|timing| {
+            let bars: Vec<(&str, f64)> = timed_parts(timing)
+                .map(|(part, stats, _)| (part, stats.median_nanos.max(1.0)))
+                .collect();
+            (timing, bars)
+        })
+        .filter(|(_, bars)| !bars.is_empty())
+        .collect();
+
+    let values = days
+        .iter()
+        .flat_map(|(_, bars)| bars.iter().map(|(_, v)| *v));
+    let (min, max) = values.fold((f64::MAX, 1.0_f64), |(min, max), v| {
+        (min.min(v), max.max(v))
+    });
+    // the axis spans whole powers of ten, from 1ns up to ~100 years.
+    let lo = min.log10().floor().clamp(0.0, 18.0) as u32;
+    let hi = (max.log10().ceil() as u32).clamp(lo + 1, 19);
+    let x = |nanos: f64| {
+        LABEL_WIDTH + (nanos.log10() - f64::from(lo)) / f64::from(hi - lo) * CHART_WIDTH
+    };
+
+    let rows: usize = days.iter().map(|(_, bars)| bars.len()).sum();
+    #[allow(clippy::cast_precision_loss)]
+    let height = AXIS_HEIGHT * 2.0 + rows as f64 * ROW_HEIGHT + days.len() as f64 * GROUP_GAP;
+    let width = LABEL_WIDTH + CHART_WIDTH + VALUE_WIDTH;
+
+    let mut svg = vec![format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif" font-size="11">"#
+    )];
+
+    for exponent in lo..=hi {
+        let tick_x = x(10_f64.powi(exponent as i32));
+        let label = format!("{:?}", Duration::from_nanos(10_u64.pow(exponent)));
+        svg.push(format!(
+            r##"<line x1="{tick_x:.1}" y1="{AXIS_HEIGHT}" x2="{tick_x:.1}" y2="{:.1}" stroke="#ddd"/>"##,
+            height - AXIS_HEIGHT
+        ));
+        svg.push(format!(
+            r#"<text x="{tick_x:.1}" y="{:.1}" text-anchor="middle">{}</text>"#,
+            AXIS_HEIGHT - 8.0,
+            escape_html(&label)
+        ));
+    }
+
+    let mut y = AXIS_HEIGHT + GROUP_GAP / 2.0;
+    for (timing, bars) in &days {
+        svg.push(format!(
+            r#"<text x="{:.1}" y="{:.1}" text-anchor="end" font-weight="bold">Day {}</text>"#,
+            LABEL_WIDTH - 8.0,
+            y + BAR_HEIGHT - 2.0,
+            timing.day.into_inner()
+        ));
+
+        for (part, nanos) in bars {
+            let time = format!("{:.1?}", Duration::from_nanos(*nanos as u64));
+            let bar_width = (x(*nanos) - LABEL_WIDTH).max(1.0);
+            svg.push(format!(
+                r#"<rect x="{LABEL_WIDTH}" y="{y:.1}" width="{bar_width:.1}" height="{BAR_HEIGHT}" fill="{}"><title>Day {} · {}: {}</title></rect>"#,
+                part_color(part),
+                timing.day.into_inner(),
+                part_label(part),
+                escape_html(&time)
+            ));
+            svg.push(format!(
+                r#"<text x="{:.1}" y="{:.1}">{}</text>"#,
+                LABEL_WIDTH + bar_width + 4.0,
+                y + BAR_HEIGHT - 2.0,
+                escape_html(&time)
+            ));
+            y += ROW_HEIGHT;
+        }
+
+        y += GROUP_GAP;
+    }
+
+    let mut legend_x = LABEL_WIDTH;
+    for part in ["parse", "1", "2"] {
+        svg.push(format!(
+            r#"<rect x="{legend_x:.1}" y="{:.1}" width="{BAR_HEIGHT}" height="{BAR_HEIGHT}" fill="{}"/>"#,
+            height - AXIS_HEIGHT + 8.0,
+            part_color(part)
+        ));
+        svg.push(format!(
+            r#"<text x="{:.1}" y="{:.1}">{}</text>"#,
+            legend_x + BAR_HEIGHT + 4.0,
+            height - AXIS_HEIGHT + 18.0,
+            part_label(part)
+        ));
+        legend_x += 80.0;
+    }
+
+    svg.push("</svg>".into());
+    svg.join("\n")
+}
+
+fn html(year: Year, timings: &Timings) -> String {
+    let environment = timings
+        .environment
+        .as_ref()
+        .map(|environment| {
+            format!(
+                "<p><small>Measured on {}.</small></p>\n",
+                escape_html(&environment.describe())
+            )
+        })
+        .unwrap_or_default();
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Benchmarks ({year})</title>
+<style>body {{ font-family: sans-serif; margin: 2em; }}</style>
+</head>
+<body>
+<h1>Benchmarks ({year})</h1>
+<p>Total: {:.2}ms, median time of every part on a log scale.</p>
+{}
+{environment}</body>
+</html>
+"#,
+        timings.total_millis(),
+        svg_chart(timings)
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{Column, ReportFormat};
+    use crate::{
+        day,
+        template::{
+            alloc::Memory,
+            fixtures::{stats, timing},
+            timings::{Timing, Timings},
+        },
+        year,
+    };
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    part_1: Some("1.0µs".into()),
+                    part_2: Some("2.0ms".into()),
+                    part_1_stats: stats(1_000.0),
+                    part_2_stats: stats(2_000_000.0),
+                    part_1_memory: Some(Memory {
+                        peak_bytes: 2048,
+                        allocations: 3,
+                    }),
+                    total_nanos: 2_001_000.0,
+                    ..timing(day!(1))
+                },
+                Timing {
+                    part_1: Some("5.0s".into()),
+                    total_nanos: 5e9,
+                    timed_out: true,
+                    ..timing(day!(2))
+                },
+            ],
+            ..Timings::default()
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(
+            ReportFormat::from_str("markdown").unwrap(),
+            ReportFormat::Markdown(Column::DEFAULT.to_vec())
+        );
+        assert_eq!(
+            ReportFormat::from_str("markdown:day,total,memory_1").unwrap(),
+            ReportFormat::Markdown(vec![Column::Day, Column::Total, Column::Memory1])
+        );
+        assert_eq!(ReportFormat::from_str("csv").unwrap(), ReportFormat::Csv);
+        assert_eq!(ReportFormat::from_str("html").unwrap(), ReportFormat::Html);
+        assert_eq!(ReportFormat::from_str("pdf").is_err(), true);
+        assert_eq!(ReportFormat::from_str("markdown:day,speed").is_err(), true);
+        assert_eq!(ReportFormat::from_str("csv:day").is_err(), true);
+    }
+
+    #[test]
+    fn renders_markdown_columns() {
+        let format = ReportFormat::from_str("markdown:day,part_2,total,memory_1").unwrap();
+        let report = format.render(year!(2025), &get_mock_timings());
+        let expected = [
+            "# Benchmarks (2025)",
+            "",
+            "| Day | Part 2 | Total | Memory 1 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2025-01.rs) | `2.0ms` | `2.0ms` | `2.0 KiB` (3 allocs) |",
+            "| [Day 2](./src/bin/2025-02.rs) | `timed out` | `5.0s` | `-` |",
+            "",
+            "**Total: 5002.00ms**",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(report, expected);
+    }
+
+    #[test]
+    fn renders_csv_rows_per_part() {
+        let report = ReportFormat::Csv.render(year!(2025), &get_mock_timings());
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].starts_with("day,part,median_nanos,"), true);
+        assert_eq!(lines[1], "01,1,1000,1000,1000,1000,0,0,10,0,2048,3");
+        assert_eq!(lines[2], "01,2,2000000,2000000,2000000,2000000,0,0,10,0,,");
+    }
+
+    #[test]
+    fn renders_json_with_year() {
+        let report = ReportFormat::Json.render(year!(2025), &get_mock_timings());
+        let timings = Timings::try_from(report.clone()).unwrap();
+        assert_eq!(timings.data.len(), 2);
+        assert_eq!(report.contains(r#""year": 2025"#), true);
+    }
+
+    #[test]
+    fn renders_log_scale_chart() {
+        let report = ReportFormat::Html.render(year!(2025), &get_mock_timings());
+        assert_eq!(report.starts_with("<!DOCTYPE html>"), true);
+        assert_eq!(report.contains("<svg"), true);
+        // ticks span the powers of ten between the fastest and the slowest part.
+        assert_eq!(report.contains(">1µs</text>"), true);
+        assert_eq!(report.contains(">10ms</text>"), true);
+        assert_eq!(report.contains(">100ns</text>"), false);
+        assert_eq!(report.matches("<rect").count(), 2 + 3);
+        assert_eq!(
+            report.contains("<title>Day 1 · Part 2: 2.0ms</title>"),
+            true
+        );
+    }
+}
@@ -1,5 +1,46 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::{process, time::Duration};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::run_multi::{Executor, has_failures, run_multi};
+use crate::template::selection::DaySelection;
+use crate::template::timeouts::DayTimeouts;
+use crate::template::timings::Timings;
+use crate::template::{
+    Year,
+    runner::{RunOptions, Solution},
+};
+
+pub fn handle(
+    solutions: &[Solution],
+    year: Year,
+    selection: &DaySelection,
+    is_release: bool,
+    isolated: Option<&DayTimeouts>,
+    part_timeout: Option<Duration>,
+    jobs: usize,
+) {
+    let days_to_run = match selection.resolve(year, &Timings::read_from_file(year)) {
+        Ok(days) => days,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let executor = match isolated {
+        Some(timeouts) => Executor::Isolated {
+            is_release,
+            timeouts,
+            jobs,
+        },
+        None => Executor::InProcess(solutions),
+    };
+
+    let options = RunOptions {
+        part_timeout,
+        ..RunOptions::default()
+    };
+
+    if has_failures(&run_multi(year, &days_to_run, executor, options)) {
+        process::exit(1);
+    }
 }
@@ -1,16 +1,44 @@
-use crate::template::{Day, aoc_cli};
-use std::process;
+use crate::template::{Day, Year, aoc_client::AocClient, data_dir};
+use std::{fs, io, path::Path, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
-        );
+pub fn handle(year: Year, day: Day) {
+    let client = match AocClient::from_env(year) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("failed to set up Advent of Code client: {e}");
+            process::exit(1);
+        }
+    };
+
+    let input_path = data_dir(year).join("inputs").join(format!("{day}.txt"));
+    let puzzle_path = data_dir(year).join("puzzles").join(format!("{day}.md"));
+
+    let result = client
+        .input(day)
+        .and_then(|input| Ok(write_file(&input_path, &input)?))
+        .and_then(|()| client.puzzle(day))
+        .and_then(|puzzle| Ok(write_file(&puzzle_path, &puzzle)?));
+
+    if let Err(e) = result {
+        eprintln!("failed to download day {day}: {e}");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    println!("---");
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+}
+
+/// Write a file, creating the data folders of a year if necessary.
+pub fn write_file(path: &Path, contents: &str) -> Result<(), io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
 }
@@ -0,0 +1,97 @@
+use std::{fs, process};
+
+use crate::template::{
+    Day, Year, bin_name, bin_path, commands::download, data_dir, examples, puzzle_examples,
+};
+
+pub fn handle(year: Year, day: Day, dry_run: bool, overwrite: bool) {
+    let puzzle_path = data_dir(year).join("puzzles").join(format!("{day}.md"));
+    let Ok(puzzle) = fs::read_to_string(&puzzle_path) else {
+        eprintln!(
+            "Could not read puzzle \"{}\", run `cargo download {day} --year {year}` first.",
+            puzzle_path.display()
+        );
+        process::exit(1);
+    };
+
+    let found = puzzle_examples::analyze(&puzzle);
+    if found.is_empty() {
+        println!("No examples found in \"{}\".", puzzle_path.display());
+        return;
+    }
+
+    let module_path = bin_path(year, day);
+    let mut module = fs::read_to_string(&module_path).ok();
+
+    for (i, example) in found.iter().enumerate() {
+        // NOTE: the first example is the default example of the day, further examples are numbered from 2.
+        let number = (i > 0).then(|| u8::try_from(i + 1).unwrap_or(u8::MAX));
+        let file_name = match number {
+            Some(number) => format!("{day}-{number}.txt"),
+            None => format!("{day}.txt"),
+        };
+        let example_path = data_dir(year).join("examples").join(file_name);
+
+        println!("Example \"{}\":", example_path.display());
+        for (part, answer) in [(1, &example.expected.part_1), (2, &example.expected.part_2)] {
+            if let Some(answer) = answer {
+                println!("  part {part}: {answer}");
+            }
+        }
+        for line in example.input.lines() {
+            println!("  | {line}");
+        }
+
+        let is_empty = fs::read_to_string(&example_path).map_or(true, |s| s.trim().is_empty());
+        if !is_empty && !overwrite {
+            println!("  skipped, the example exists already. Pass --overwrite to replace it.");
+            continue;
+        }
+
+        // tests are only updated along with their example.
+        for (part, test) in [(1, "test_part_one"), (2, "test_part_two")] {
+            let Some(answer) = example.expected.get(part) else {
+                continue;
+            };
+            if let Some(updated) = module
+                .as_deref()
+                .and_then(|source| puzzle_examples::update_test(source, test, answer, number))
+            {
+                println!("  updates `{test}` to expect {answer}");
+                module = Some(updated);
+            }
+        }
+
+        if dry_run {
+            continue;
+        }
+
+        let contents = examples::render(&example.expected, &example.input);
+        if let Err(e) = download::write_file(&example_path, &contents) {
+            eprintln!("Failed to write example: {e}");
+            process::exit(1);
+        }
+    }
+
+    if dry_run {
+        println!("---");
+        println!("🎄 Dry run, nothing was written.");
+        return;
+    }
+
+    if let Some(module) = module
+        && let Err(e) = fs::write(&module_path, module)
+    {
+        eprintln!(
+            "Failed to update tests in \"{}\": {e}",
+            module_path.display()
+        );
+        process::exit(1);
+    }
+
+    println!("---");
+    println!(
+        "🎄 Type `cargo test --bin {}` to check them.",
+        bin_name(year, day)
+    );
+}
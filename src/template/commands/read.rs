@@ -1,17 +1,31 @@
 use std::process;
 
-use crate::template::{Day, aoc_cli};
+use crate::template::{Day, Year, aoc_client::AocClient, commands::download, data_dir};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
+pub fn handle(year: Year, day: Day) {
+    let client = match AocClient::from_env(year) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("failed to set up Advent of Code client: {e}");
+            process::exit(1);
+        }
+    };
+
+    let puzzle = match client.puzzle(day) {
+        Ok(puzzle) => puzzle,
+        Err(e) => {
+            eprintln!("failed to read day {day}: {e}");
+            process::exit(1);
+        }
+    };
+
+    let puzzle_path = data_dir(year).join("puzzles").join(format!("{day}.md"));
+    if let Err(e) = download::write_file(&puzzle_path, &puzzle) {
         eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            "failed to write puzzle to \"{}\": {e}",
+            puzzle_path.display()
         );
-        process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    println!("{puzzle}");
 }
@@ -1,15 +1,15 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{Day, Year, bin_path, data_dir, module_templates};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+pub use module_templates::DEFAULT_TEMPLATE;
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+fn safe_create_file(path: &Path, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -19,18 +19,31 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Create an empty data file, keeping the contents of an existing file, e.g. a downloaded input.
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    OpenOptions::new().append(true).create(true).open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(year: Year, day: Day, overwrite: bool, template: &str) {
+    let template = match module_templates::load(template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to load template: {e}");
+            process::exit(1);
+        }
+    };
+
+    let puzzle_path = data_dir(year).join("puzzles").join(format!("{day}.md"));
+    let title = fs::read_to_string(puzzle_path)
+        .ok()
+        .and_then(|puzzle| module_templates::puzzle_title(&puzzle));
+
+    let input_path = data_dir(year).join("inputs").join(format!("{day}.txt"));
+    let example_path = data_dir(year).join("examples").join(format!("{day}.txt"));
+    let module_path = bin_path(year, day);
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,13 +53,11 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file
+        .write_all(module_templates::render(&template, year, day, title.as_deref()).as_bytes())
+    {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{}\"", module_path.display());
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -54,9 +65,13 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
+    let input_exists = input_path.exists();
     match create_file(&input_path) {
+        Ok(_) if input_exists => {
+            println!("Kept existing input file \"{}\"", input_path.display());
+        }
         Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+            println!("Created empty input file \"{}\"", input_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
@@ -64,9 +79,13 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
+    let example_exists = example_path.exists();
     match create_file(&example_path) {
+        Ok(_) if example_exists => {
+            println!("Kept existing example file \"{}\"", example_path.display());
+        }
         Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+            println!("Created empty example file \"{}\"", example_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
@@ -75,5 +94,5 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
 }
@@ -1,9 +1,20 @@
-use std::process::{Command, Stdio};
+use std::{
+    process::{Command, Stdio},
+    time::Duration,
+};
 
-use crate::template::Day;
+use crate::template::{Day, Year, bin_name, input::InputSource, runner::RunOptions};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    year: Year,
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    part_timeout: Option<Duration>,
+    input: &InputSource,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), bin_name(year, day)];
 
     if dhat {
         cmd_args.extend([
@@ -18,10 +29,13 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
-    }
+    let options = RunOptions {
+        submit: submit_part,
+        part_timeout,
+        ..RunOptions::default()
+    };
+    cmd_args.extend(options.to_args());
+    cmd_args.extend(input.to_args());
 
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
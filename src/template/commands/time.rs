@@ -1,34 +1,123 @@
-use std::collections::HashSet;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+};
 
-use crate::template::run_multi::run_multi;
+use crate::template::benchmark_report::ReportFormat;
+use crate::template::config;
+use crate::template::environment::Environment;
+use crate::template::history::{self, History, HistoryRun};
+use crate::template::run_multi::{Executor, has_failures, run_multi};
+use crate::template::runner::{RunOptions, Solution};
+use crate::template::selection::DaySelection;
+use crate::template::timeouts::DayTimeouts;
 use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
-
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
-
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
-            }
+use crate::template::{Year, readme_benchmarks};
+
+/// What is done with the timings of a run, besides printing them.
+pub struct Outputs {
+    /// Store the timings, append them to the history and update the readme.
+    pub store: bool,
+    /// Compare the timings against the history, regressions beyond the threshold in percent fail the run.
+    pub compare_threshold: Option<f64>,
+    /// Export the timings of the year, to the given file or to the default path of the format.
+    pub report: Option<(ReportFormat, Option<PathBuf>)>,
+}
+
+fn write_report(path: &Path, contents: &str) -> Result<(), io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+pub fn handle(
+    solutions: &[Solution],
+    year: Year,
+    selection: &DaySelection,
+    isolated: Option<&DayTimeouts>,
+    options: RunOptions,
+    outputs: &Outputs,
+) {
+    let stored_timings = Timings::read_from_file(year);
+
+    let mut days_to_run = match selection.resolve(year, &stored_timings) {
+        Ok(days) => days,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    // days excluded from benchmarks in `aoc.toml` only run if they are selected by their number.
+    let config = config::get();
+    days_to_run.retain(|&day| {
+        let skip = config.day(year, day).skip_benchmark && !selection.names(day);
+        if skip {
+            println!("Skipping day {day}, it is excluded from benchmarks in aoc.toml.");
+        }
+        !skip
+    });
+
+    let executor = match isolated {
+        Some(timeouts) => Executor::Isolated {
+            is_release: true,
+            timeouts,
+            jobs: 1,
         },
-        |day| HashSet::from([day]),
-    );
+        None => Executor::InProcess(solutions),
+    };
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let reports = run_multi(year, &days_to_run, executor, options);
+    // in-process runs are built with the profile of the main binary, isolated runs always in release mode.
+    let release = isolated.is_some() || !cfg!(debug_assertions);
+    let timings = Timings {
+        environment: Some(Environment::capture(release)),
+        ..Timings::from_reports(&reports)
+    };
 
-    if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+    let mut regressions = 0;
 
+    if let Some(threshold) = outputs.compare_threshold {
+        match History::read_from_file(year) {
+            Ok(history) => {
+                println!();
+                regressions = history::print_comparisons(&history.compare(&timings), threshold);
+            }
+            Err(e) => {
+                eprintln!("Failed to read benchmark history: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    let merged_timings = stored_timings.merge(&timings);
+
+    if let Some((format, path)) = &outputs.report {
+        let path = path.clone().unwrap_or_else(|| format.default_path(year));
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match write_report(&path, &format.render(year, &merged_timings)) {
+            Ok(()) => println!("Wrote benchmark report to {}.", path.display()),
+            Err(e) => eprintln!("Failed to write benchmark report: {e}"),
+        }
+    }
+
+    if outputs.store {
+        if let Err(e) = History::append_to_file(year, &HistoryRun::capture(timings.clone())) {
+            eprintln!("Failed to append to benchmark history: {e}");
+        }
+
+        merged_timings.store_file(year).unwrap();
+
+        if merged_timings.mixed_environments {
+            eprintln!(
+                "Warning: some of the stored timings were measured on a different machine, run `cargo time --all --store` to measure all days on this one."
+            );
+        }
+
+        println!();
+        match readme_benchmarks::update(year, merged_timings) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +126,13 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if regressions > 0 {
+        eprintln!("{regressions} part(s) regressed beyond the threshold.");
+        process::exit(1);
+    }
+
+    if has_failures(&reports) {
+        process::exit(1);
+    }
 }
@@ -0,0 +1,125 @@
+use std::{collections::HashSet, process, time::Duration};
+
+use crate::template::report::{PARSE_PART, PartReport, PartStatus};
+use crate::template::run_multi::{Executor, run_multi};
+use crate::template::runner::{RunOptions, Solution};
+use crate::template::timeouts::DayTimeouts;
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day, Year, answers};
+
+/// Outcome of checking a single part against its stored answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Verdict {
+    Pass,
+    Fail,
+    /// The part panicked, timed out or returned an error, see [`PartStatus::is_failure`].
+    Failed,
+    Unsolved,
+    NoAnswer,
+}
+
+impl Verdict {
+    fn symbol(self) -> &'static str {
+        match self {
+            Verdict::Pass => "✔",
+            Verdict::Fail => "✖",
+            Verdict::Failed => "!",
+            Verdict::Unsolved => "-",
+            Verdict::NoAnswer => "?",
+        }
+    }
+}
+
+fn verdict(report: Option<&PartReport>, stored: Option<&str>) -> Verdict {
+    match (report, stored) {
+        (Some(report), _) if report.status.is_failure() => Verdict::Failed,
+        (_, None) => Verdict::NoAnswer,
+        (Some(report), Some(stored)) if report.status == PartStatus::Solved => {
+            match &report.answer {
+                Some(answer) if answers::matches(stored, answer) => Verdict::Pass,
+                _ => Verdict::Fail,
+            }
+        }
+        (_, Some(_)) => Verdict::Unsolved,
+    }
+}
+
+pub fn handle(
+    solutions: &[Solution],
+    year: Year,
+    day: Option<Day>,
+    isolated: Option<&DayTimeouts>,
+    part_timeout: Option<Duration>,
+) {
+    // only days with at least one stored answer can be verified.
+    let days_to_run: HashSet<Day> = day.map_or_else(
+        || {
+            year.days()
+                .filter(|day| (1..=2).any(|part| answers::read(year, *day, part).is_some()))
+                .collect()
+        },
+        |day| HashSet::from([day]),
+    );
+
+    if days_to_run.is_empty() {
+        println!("No stored answers to verify against.");
+        return;
+    }
+
+    let executor = match isolated {
+        Some(timeouts) => Executor::Isolated {
+            is_release: true,
+            timeouts,
+            jobs: 1,
+        },
+        None => Executor::InProcess(solutions),
+    };
+
+    let options = RunOptions {
+        part_timeout,
+        ..RunOptions::default()
+    };
+
+    let reports = run_multi(year, &days_to_run, executor, options);
+
+    println!();
+    println!("{ANSI_BOLD}Verification{ANSI_RESET}");
+    println!();
+    println!("| Day | Part 1 | Part 2 |");
+    println!("| :---: | :---: | :---: |");
+
+    let mut failures = 0;
+
+    for day in year.days().filter(|day| days_to_run.contains(day)) {
+        let verdicts = [1, 2].map(|part| {
+            // parts are not run if the shared parse step failed, that failure is theirs too.
+            let report = reports
+                .iter()
+                .find(|r| r.day == day && r.part == part)
+                .or_else(|| {
+                    reports
+                        .iter()
+                        .find(|r| r.day == day && r.part == PARSE_PART && r.status.is_failure())
+                });
+            verdict(report, answers::read(year, day, part).as_deref())
+        });
+
+        failures += verdicts
+            .iter()
+            .filter(|v| matches!(v, Verdict::Fail | Verdict::Failed | Verdict::Unsolved))
+            .count();
+
+        println!(
+            "| {day} | {} | {} |",
+            verdicts[0].symbol(),
+            verdicts[1].symbol()
+        );
+    }
+
+    println!();
+    println!("✔ correct, ✖ wrong answer, ! failed to run, - not solved, ? no stored answer");
+
+    if failures > 0 {
+        eprintln!("{failures} part(s) failed verification.");
+        process::exit(1);
+    }
+}
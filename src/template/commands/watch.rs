@@ -0,0 +1,108 @@
+use std::{
+    process::{self, Command, Stdio},
+    thread,
+    time::Duration,
+};
+
+use crate::template::{
+    ANSI_BOLD, ANSI_RESET, Day, Year, bin_name, bin_path,
+    report::PartReport,
+    run_multi::child_commands,
+    runner::RunOptions,
+    watch::{self, Snapshot},
+};
+
+/// Interval at which the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Run the tests of a day, which check its examples, then its puzzle input.
+/// Returns the reports of the input run, `None` if the solution could not be built.
+fn run(year: Year, day: Day, release: bool) -> Option<Vec<PartReport>> {
+    let bin = bin_name(year, day);
+
+    println!("{ANSI_BOLD}Examples{ANSI_RESET}");
+    let tests = Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &bin])
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status();
+    match tests {
+        Ok(status) if status.success() => println!("✔ Example tests passed."),
+        Ok(_) => println!("✖ Example tests failed."),
+        Err(e) => eprintln!("Failed to run tests: {e}"),
+    }
+
+    println!();
+    println!("{ANSI_BOLD}Input{ANSI_RESET}");
+    let executable = match child_commands::build_solution(year, day, release) {
+        Ok(executable) => executable?,
+        Err(e) => {
+            eprintln!("Failed to build {bin}: {e}");
+            return None;
+        }
+    };
+
+    match child_commands::run_executable(year, day, &executable, RunOptions::default(), None, None)
+    {
+        Ok(reports) => Some(reports),
+        Err(e) => {
+            eprintln!("Failed to run {bin}: {e}");
+            None
+        }
+    }
+}
+
+fn print_answers(previous: Option<&[PartReport]>, reports: &[PartReport]) {
+    println!();
+    println!("{ANSI_BOLD}Answers{ANSI_RESET}");
+    for line in watch::diff_answers(previous, reports) {
+        println!("{line}");
+    }
+}
+
+pub fn handle(year: Year, day: Day, release: bool) {
+    if !bin_path(year, day).exists() {
+        eprintln!(
+            "Day {day} has not been scaffolded yet, run `cargo scaffold {day} --year {year}` first."
+        );
+        process::exit(1);
+    }
+
+    let mut snapshot = Snapshot::take(&watch::paths(year, day));
+    let mut previous = run(year, day, release);
+    if let Some(reports) = &previous {
+        print_answers(None, reports);
+    }
+
+    loop {
+        println!();
+        println!("🎄 Watching for changes, press Ctrl+C to stop.");
+
+        let changed = loop {
+            thread::sleep(POLL_INTERVAL);
+
+            // NOTE: paths are resolved on every poll, so that new examples are picked up.
+            let current = Snapshot::take(&watch::paths(year, day));
+            let changed: Vec<String> = snapshot
+                .changed(&current)
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+
+            snapshot = current;
+            if !changed.is_empty() {
+                break changed;
+            }
+        };
+
+        println!();
+        println!("{ANSI_BOLD}Changed:{ANSI_RESET} {}", changed.join(", "));
+        println!("------");
+
+        // a failed build keeps the answers of the last successful run to compare against.
+        if let Some(reports) = run(year, day, release) {
+            print_answers(previous.as_deref(), &reports);
+            previous = Some(reports);
+        }
+    }
+}
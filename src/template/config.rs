@@ -0,0 +1,450 @@
+/// Module that loads the project configuration from `aoc.toml` in the root of the crate.
+/// Every setting is optional, a missing file uses the defaults of the template:
+///
+/// ```toml
+/// # the year of commands without `--year`, takes precedence over `AOC_YEAR`.
+/// year = 2025
+///
+/// [paths]
+/// data = "data"
+/// readme = "README.md"
+/// templates = "templates"  # user-defined templates of `scaffold --template`.
+///
+/// [readme]
+/// # the benchmark table is placed between two `<!--- benchmarking table --->` comments.
+/// marker = "benchmarking table"
+///
+/// [benchmark]
+/// warmup_ms = 100
+/// budget_ms = 1000
+/// min_samples = 10
+/// max_samples = 10000
+///
+/// [day.2025-12]
+/// timeout = 300          # seconds, for `--isolated` runs.
+/// skip_benchmark = true  # `time` only runs the day if it is selected on its own.
+/// part_1 = "1234"        # expected answers, checked by `verify` if no answer is stored.
+/// ```
+///
+/// Command-line flags take precedence over the file, except that the timeout of a day wins over a default `--timeout`.
+/// Solution binaries stay in `src/bin/`, where cargo and `build.rs` discover them, see [`bin_path`](crate::template::bin_path).
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::OnceLock,
+    time::Duration,
+};
+
+use serde::Deserialize;
+
+use crate::template::{Day, Year, runner::BenchConfig};
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Settings that apply to a single day of a year.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DayConfig {
+    /// Budget of an isolated run, unless `--timeout` sets one for this day, see [`DayTimeouts::with_fallbacks`](crate::template::timeouts::DayTimeouts::with_fallbacks).
+    pub timeout: Option<Duration>,
+    pub skip_benchmark: bool,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl DayConfig {
+    /// The expected answer of a part.
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    pub year: Option<Year>,
+    /// Holds a folder per year, e.g. `data/2025`.
+    pub data_dir: PathBuf,
+    pub readme: PathBuf,
+    /// Holds user-defined templates, e.g. `templates/grid.txt`, see `module_templates`.
+    pub template_dir: PathBuf,
+    /// Name of the comment that marks the benchmark table, see `readme_benchmarks`.
+    pub readme_marker: String,
+    pub bench: BenchConfig,
+    pub days: HashMap<(Year, Day), DayConfig>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            year: None,
+            data_dir: PathBuf::from("data"),
+            readme: PathBuf::from("README.md"),
+            template_dir: PathBuf::from("templates"),
+            readme_marker: "benchmarking table".into(),
+            bench: BenchConfig::default(),
+            days: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ConfigError(String);
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{CONFIG_FILE}: {}", self.0)
+    }
+}
+
+impl Error for ConfigError {}
+
+impl From<io::Error> for ConfigError {
+    fn from(e: io::Error) -> Self {
+        ConfigError(format!("could not read file: {e}"))
+    }
+}
+
+impl From<toml::de::Error> for ConfigError {
+    fn from(e: toml::de::Error) -> Self {
+        ConfigError(e.to_string())
+    }
+}
+
+/// Load `aoc.toml` once. `main` loads it up-front, so that an invalid config is reported before running anything.
+pub fn load() -> Result<&'static Config, ConfigError> {
+    if let Some(config) = CONFIG.get() {
+        return Ok(config);
+    }
+    let config = Config::read(Path::new(CONFIG_FILE))?;
+    Ok(CONFIG.get_or_init(|| config))
+}
+
+/// The loaded config, e.g. in solution binaries, which do not go through `main`.
+/// Panics if `aoc.toml` is invalid.
+pub fn get() -> &'static Config {
+    load().unwrap_or_else(|e| panic!("{e}"))
+}
+
+impl Config {
+    /// Read a config file, a missing file yields the default config.
+    pub fn read(path: &Path) -> Result<Self, ConfigError> {
+        match fs::read_to_string(path) {
+            Ok(contents) => contents.parse(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// The settings of a day, the defaults if it has none.
+    pub fn day(&self, year: Year, day: Day) -> DayConfig {
+        self.days.get(&(year, day)).cloned().unwrap_or_default()
+    }
+
+    /// The marker comment of the benchmark table, e.g. `<!--- benchmarking table --->`.
+    pub fn readme_marker(&self, year: Option<Year>) -> String {
+        match year {
+            Some(year) => format!("<!--- {} {year} --->", self.readme_marker),
+            None => format!("<!--- {} --->", self.readme_marker),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The layout of `aoc.toml`, validated when it is converted into a [`Config`].
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct File {
+    year: Option<u16>,
+    paths: Paths,
+    readme: Readme,
+    benchmark: Benchmark,
+    /// Keyed by the id of a day, e.g. `2025-12`.
+    day: HashMap<String, DayFile>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Paths {
+    data: Option<String>,
+    readme: Option<String>,
+    templates: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Readme {
+    marker: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Benchmark {
+    warmup_ms: Option<u64>,
+    budget_ms: Option<u64>,
+    min_samples: Option<u32>,
+    max_samples: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct DayFile {
+    timeout: Option<f64>,
+    skip_benchmark: bool,
+    part_1: Option<AnswerValue>,
+    part_2: Option<AnswerValue>,
+}
+
+/// Expected answers can be written as a number or as a string.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum AnswerValue {
+    Integer(i64),
+    String(String),
+}
+
+impl AnswerValue {
+    fn into_answer(self) -> String {
+        match self {
+            AnswerValue::Integer(answer) => answer.to_string(),
+            AnswerValue::String(answer) => answer,
+        }
+    }
+}
+
+/// Parse the id of a day table, e.g. `2025-12`.
+fn parse_day_id(id: &str) -> Option<(Year, Day)> {
+    let (year, day) = id.split_once('-')?;
+    let year: Year = year.parse().ok()?;
+    let day: Day = day.parse().ok()?;
+    year.contains(day).then_some((year, day))
+}
+
+fn non_empty(key: &str, value: Option<String>) -> Result<Option<String>, ConfigError> {
+    match value {
+        Some(value) if value.is_empty() => Err(ConfigError(format!(
+            "expected `{key}` to be a non-empty string"
+        ))),
+        value => Ok(value),
+    }
+}
+
+fn positive<T: Default + PartialEq>(key: &str, value: Option<T>) -> Result<Option<T>, ConfigError> {
+    match value {
+        Some(value) if value == T::default() => Err(ConfigError(format!(
+            "expected `{key}` to be a positive integer"
+        ))),
+        value => Ok(value),
+    }
+}
+
+impl TryFrom<File> for Config {
+    type Error = ConfigError;
+
+    fn try_from(file: File) -> Result<Self, Self::Error> {
+        let mut config = Config::default();
+
+        if let Some(year) = file.year {
+            let year = Year::new(year).ok_or_else(|| {
+                ConfigError(format!(
+                    "expected `year` to be a year of advent, found {year}"
+                ))
+            })?;
+            config.year = Some(year);
+        }
+
+        if let Some(data) = non_empty("paths.data", file.paths.data)? {
+            config.data_dir = data.into();
+        }
+        if let Some(readme) = non_empty("paths.readme", file.paths.readme)? {
+            config.readme = readme.into();
+        }
+        if let Some(templates) = non_empty("paths.templates", file.paths.templates)? {
+            config.template_dir = templates.into();
+        }
+        if let Some(marker) = non_empty("readme.marker", file.readme.marker)? {
+            config.readme_marker = marker;
+        }
+
+        let benchmark = file.benchmark;
+        if let Some(warmup) = positive("benchmark.warmup_ms", benchmark.warmup_ms)? {
+            config.bench.warmup = Duration::from_millis(warmup);
+        }
+        if let Some(budget) = positive("benchmark.budget_ms", benchmark.budget_ms)? {
+            config.bench.budget = Duration::from_millis(budget);
+        }
+        if let Some(min_samples) = positive("benchmark.min_samples", benchmark.min_samples)? {
+            config.bench.min_samples = min_samples;
+        }
+        if let Some(max_samples) = positive("benchmark.max_samples", benchmark.max_samples)? {
+            config.bench.max_samples = max_samples;
+        }
+        if config.bench.min_samples > config.bench.max_samples {
+            return Err(ConfigError(
+                "expecting benchmark.min_samples <= benchmark.max_samples".into(),
+            ));
+        }
+
+        for (id, day) in file.day {
+            let key = parse_day_id(&id).ok_or_else(|| {
+                ConfigError(format!(
+                    "invalid day `{id}`, expecting a day of an event like `[day.2025-12]`"
+                ))
+            })?;
+
+            let timeout = day
+                .timeout
+                .map(|secs| {
+                    Duration::try_from_secs_f64(secs)
+                        .ok()
+                        .filter(|timeout| !timeout.is_zero())
+                        .ok_or_else(|| {
+                            ConfigError(format!(
+                                "expected `day.{id}.timeout` to be a positive number of seconds"
+                            ))
+                        })
+                })
+                .transpose()?;
+
+            config.days.insert(
+                key,
+                DayConfig {
+                    timeout,
+                    skip_benchmark: day.skip_benchmark,
+                    part_1: day.part_1.map(AnswerValue::into_answer),
+                    part_2: day.part_2.map(AnswerValue::into_answer),
+                },
+            );
+        }
+
+        Ok(config)
+    }
+}
+
+impl FromStr for Config {
+    type Err = ConfigError;
+
+    fn from_str(contents: &str) -> Result<Self, Self::Err> {
+        toml::from_str::<File>(contents)?.try_into()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{path::PathBuf, time::Duration};
+
+    use super::{Config, DayConfig};
+    use crate::{day, year};
+
+    #[test]
+    fn parses_config() {
+        let config: Config = r#"
+# comment
+year = 2024
+
+[paths]
+data = "puzzles" # comment
+readme = "docs/README.md"
+templates = "aoc/templates"
+
+[readme]
+marker = "timings # 1"
+
+[benchmark]
+budget_ms = 2_000
+max_samples = 500
+
+[day.2024-12]
+timeout = 0.5
+skip_benchmark = true
+part_1 = 1234
+part_2 = "ABC\"D"
+
+[day.2024-11]
+timeout = 60
+"#
+        .parse()
+        .unwrap();
+
+        assert_eq!(config.year, Some(year!(2024)));
+        assert_eq!(config.data_dir, PathBuf::from("puzzles"));
+        assert_eq!(config.readme, PathBuf::from("docs/README.md"));
+        assert_eq!(config.template_dir, PathBuf::from("aoc/templates"));
+        assert_eq!(config.readme_marker(None), "<!--- timings # 1 --->");
+        assert_eq!(
+            config.readme_marker(Some(year!(2024))),
+            "<!--- timings # 1 2024 --->"
+        );
+        assert_eq!(config.bench.budget, Duration::from_secs(2));
+        assert_eq!(config.bench.max_samples, 500);
+        assert_eq!(config.bench.min_samples, 10);
+        assert_eq!(
+            config.day(year!(2024), day!(12)),
+            DayConfig {
+                timeout: Some(Duration::from_millis(500)),
+                skip_benchmark: true,
+                part_1: Some("1234".into()),
+                part_2: Some("ABC\"D".into()),
+            }
+        );
+        assert_eq!(
+            config.day(year!(2024), day!(11)).timeout,
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(config.day(year!(2024), day!(10)), DayConfig::default());
+    }
+
+    #[test]
+    fn defaults_to_template_settings() {
+        let config: Config = "".parse().unwrap();
+        assert_eq!(config, Config::default());
+        assert_eq!(config.readme_marker(None), "<!--- benchmarking table --->");
+    }
+
+    #[test]
+    fn reports_invalid_configs() {
+        let error = |contents: &str| contents.parse::<Config>().unwrap_err().to_string();
+
+        assert_eq!(
+            error("year = 2024\nyaer = 2025").contains("unknown field `yaer`"),
+            true
+        );
+        assert_eq!(
+            error("year = 2014"),
+            "aoc.toml: expected `year` to be a year of advent, found 2014"
+        );
+        assert_eq!(
+            error("[day.2025-13]"),
+            "aoc.toml: invalid day `2025-13`, expecting a day of an event like `[day.2025-12]`"
+        );
+        assert_eq!(
+            error("[day.2025-12]\ntimeout = -1"),
+            "aoc.toml: expected `day.2025-12.timeout` to be a positive number of seconds"
+        );
+        assert_eq!(
+            error("[day.2025-12]\ntimeout = 1e20"),
+            "aoc.toml: expected `day.2025-12.timeout` to be a positive number of seconds"
+        );
+        assert_eq!(
+            error("[benchmark]\nbudget_ms = 0"),
+            "aoc.toml: expected `benchmark.budget_ms` to be a positive integer"
+        );
+        assert_eq!(
+            error("[benchmark]\nmin_samples = 20\nmax_samples = 10"),
+            "aoc.toml: expecting benchmark.min_samples <= benchmark.max_samples"
+        );
+        assert_eq!(error("[paths]\ndata = \"\"").contains("non-empty"), true);
+        assert_eq!(error("[bench]").contains("unknown field `bench`"), true);
+    }
+}
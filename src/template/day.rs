@@ -2,19 +2,24 @@ use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
+#[cfg(feature = "today")]
+use crate::template::Year;
+
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
 
-/// A valid day number of advent (i.e. an integer in range 1 to 12).
+/// A valid day number of advent (i.e. an integer in range 1 to 25).
+///
+/// Not every year has 25 days, see [`Year::last_day`](crate::template::Year::last_day).
 ///
 /// # Display
 /// This value displays as a two digit number.
 ///
 /// ```
-/// # use advent_of_code::Day;
+/// # use advent_of_code::template::Day;
 /// let day = Day::new(8).unwrap();
 /// assert_eq!(day.to_string(), "08")
 /// ```
@@ -25,7 +30,7 @@ impl Day {
     /// Creates a [`Day`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub const fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 12 {
+        if day == 0 || day > 25 {
             return None;
         }
         Some(Self(day))
@@ -39,15 +44,13 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 12th of december, `None` otherwise.
-    pub fn today() -> Option<Self> {
+    /// Returns the current year and day if advent is running, `None` otherwise.
+    pub fn today() -> Option<(Year, Self)> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 12 {
-            Self::new(u8::try_from(today.day()).ok()?)
-        } else {
-            None
-        }
+        let year = Year::new(u16::try_from(today.year()).ok()?)?;
+        let day = Self::new(u8::try_from(today.day()).ok()?)?;
+        (today.month() == 12 && year.contains(day)).then_some((year, day))
     }
 }
 
@@ -88,26 +91,21 @@ impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 12")
+        f.write_str("expecting a day number between 1 and 25")
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 12th.
-pub fn all_days() -> AllDays {
-    AllDays::new()
-}
-
-/// An iterator that yields every day of advent from the 1st to the 12th.
+/// An iterator that yields every day of advent from the 1st to a given last day, see [`Year::days`](crate::template::Year::days).
 pub struct AllDays {
     current: u8,
+    last: Day,
 }
 
 impl AllDays {
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self {
-        Self { current: 1 }
+    pub fn new(last: Day) -> Self {
+        Self { current: 1, last }
     }
 }
 
@@ -115,10 +113,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 12 {
+        if self.current > self.last.0 {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 12.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above the last day.
         let day = Day(self.current);
         self.current += 1;
 
@@ -134,7 +132,7 @@ macro_rules! day {
     ($day:expr) => {
         const {
             $crate::template::Day::new($day)
-                .expect("invalid day number, expecting a value between 1 and 12")
+                .expect("invalid day number, expecting a value between 1 and 25")
         }
     };
 }
@@ -143,11 +141,11 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{Day, all_days};
+    use super::{AllDays, Day};
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = AllDays::new(Day(12));
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -0,0 +1,244 @@
+/// Module that describes the environment benchmarks were measured in, e.g. the CPU and the compiler.
+/// It is stored with the timings of a year and rendered below the benchmark table of the readme.
+use std::{collections::HashMap, fs, thread};
+
+use tinyjson::JsonValue;
+
+use crate::template::history::RUSTC_VERSION;
+
+/// The target features the solutions were compiled with, see `build.rs`.
+/// Solution binaries are built by the same cargo configuration as the main binary, so they share its target features.
+const TARGET_FEATURES: &str = env!("AOC_TARGET_FEATURES");
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Environment {
+    /// Model name of the CPU, `unknown` if it could not be read from `/proc/cpuinfo`.
+    pub cpu: String,
+    pub cores: usize,
+    /// Scaling governor of the CPU, e.g. `performance`, if it could be read.
+    pub governor: Option<String>,
+    pub rustc: String,
+    /// `release` or `debug`.
+    pub profile: String,
+    pub target_features: Vec<String>,
+    /// Whether the solutions profiled their heap, the main binary forwards `dhat-heap` to the solution binaries it builds.
+    pub dhat: bool,
+}
+
+impl Environment {
+    /// Capture the environment of the current machine, for solutions built in `release` mode or not.
+    pub fn capture(release: bool) -> Self {
+        let cpuinfo = fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+        let governor = fs::read_to_string("/sys/devices/system/cpu/cpu0/cpufreq/scaling_governor")
+            .ok()
+            .map(|governor| governor.trim().to_string())
+            .filter(|governor| !governor.is_empty());
+
+        Environment {
+            cpu: cpu_model(&cpuinfo).unwrap_or_else(|| "unknown".into()),
+            cores: thread::available_parallelism().map_or(1, usize::from),
+            governor,
+            rustc: RUSTC_VERSION.into(),
+            profile: if release { "release" } else { "debug" }.into(),
+            target_features: TARGET_FEATURES
+                .split(',')
+                .filter(|feature| !feature.is_empty())
+                .map(String::from)
+                .collect(),
+            dhat: cfg!(feature = "dhat-heap"),
+        }
+    }
+
+    /// Whether both environments describe the same hardware.
+    pub fn same_machine(&self, other: &Self) -> bool {
+        self.cpu == other.cpu && self.cores == other.cores
+    }
+
+    /// A single line summary, e.g. for the readme.
+    pub fn describe(&self) -> String {
+        let mut parts = vec![
+            format!(
+                "{} ({} {})",
+                self.cpu,
+                self.cores,
+                if self.cores == 1 { "core" } else { "cores" }
+            ),
+            self.rustc.clone(),
+            format!("{} profile", self.profile),
+        ];
+        if let Some(governor) = &self.governor {
+            parts.push(format!("{governor} governor"));
+        }
+        parts.push(format!("dhat {}", if self.dhat { "on" } else { "off" }));
+        if !self.target_features.is_empty() {
+            parts.push(format!(
+                "target features: {}",
+                self.target_features.join(", ")
+            ));
+        }
+        parts.join(" · ")
+    }
+}
+
+/// Read the model name of the first processor of a `/proc/cpuinfo` file.
+fn cpu_model(cpuinfo: &str) -> Option<String> {
+    cpuinfo
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(key, _)| key.trim() == "model name")
+        .map(|(_, value)| value.trim().to_string())
+        .filter(|model| !model.is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Environment> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Environment) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("cpu".into(), JsonValue::String(value.cpu.clone()));
+        map.insert("cores".into(), JsonValue::Number(value.cores as f64));
+        map.insert(
+            "governor".into(),
+            match &value.governor {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("rustc".into(), JsonValue::String(value.rustc.clone()));
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert(
+            "target_features".into(),
+            JsonValue::Array(
+                value
+                    .target_features
+                    .iter()
+                    .cloned()
+                    .map(JsonValue::String)
+                    .collect(),
+            ),
+        );
+        map.insert("dhat".into(), JsonValue::Boolean(value.dhat));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Environment {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected environment to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected environment.{key} to be a string."))
+        };
+
+        let cores = json
+            .get("cores")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as usize)
+            .ok_or("Expected environment.cores to be a number.")?;
+
+        let governor = json
+            .get("governor")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected environment.governor to be null or string.")?;
+
+        let target_features = json
+            .get("target_features")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .and_then(|features| {
+                features
+                    .iter()
+                    .map(|feature| feature.get::<String>().cloned())
+                    .collect::<Option<Vec<_>>>()
+            })
+            .ok_or("Expected environment.target_features to be an array of strings.")?;
+
+        let dhat = json
+            .get("dhat")
+            .and_then(|v| v.get::<bool>())
+            .copied()
+            .ok_or("Expected environment.dhat to be a boolean.")?;
+
+        Ok(Environment {
+            cpu: string("cpu")?,
+            cores,
+            governor: governor.cloned(),
+            rustc: string("rustc")?,
+            profile: string("profile")?,
+            target_features,
+            dhat,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{Environment, cpu_model};
+
+    fn mock_environment() -> Environment {
+        Environment {
+            cpu: "AMD Ryzen 7 5800X 8-Core Processor".into(),
+            cores: 16,
+            governor: Some("performance".into()),
+            rustc: "rustc 1.95.0".into(),
+            profile: "release".into(),
+            target_features: vec!["fxsr".into(), "sse".into(), "sse2".into()],
+            dhat: false,
+        }
+    }
+
+    #[test]
+    fn reads_cpu_model() {
+        let cpuinfo = "processor\t: 0\nvendor_id\t: AuthenticAMD\nmodel name\t: AMD Ryzen 7 5800X 8-Core Processor\n\nprocessor\t: 1\nmodel name\t: AMD Ryzen 7 5800X 8-Core Processor\n";
+        assert_eq!(
+            cpu_model(cpuinfo),
+            Some("AMD Ryzen 7 5800X 8-Core Processor".into())
+        );
+        assert_eq!(cpu_model("processor\t: 0\n"), None);
+    }
+
+    #[test]
+    fn describes_environment() {
+        assert_eq!(
+            mock_environment().describe(),
+            "AMD Ryzen 7 5800X 8-Core Processor (16 cores) · rustc 1.95.0 · release profile · performance governor · dhat off · target features: fxsr, sse, sse2"
+        );
+    }
+
+    #[test]
+    fn compares_machines() {
+        let environment = mock_environment();
+        let debug = Environment {
+            profile: "debug".into(),
+            ..mock_environment()
+        };
+        let laptop = Environment {
+            cores: 8,
+            ..mock_environment()
+        };
+        assert_eq!(environment.same_machine(&debug), true);
+        assert_eq!(environment.same_machine(&laptop), false);
+    }
+
+    #[test]
+    fn converts_json() {
+        let environment = mock_environment();
+        let json = JsonValue::from(&environment);
+        assert_eq!(Environment::try_from(&json), Ok(environment));
+        assert_eq!(Environment::try_from(&JsonValue::Null).is_err(), true);
+    }
+}
@@ -0,0 +1,263 @@
+/// Module that reads the examples of a day, e.g. `data/2025/examples/01.txt` and further examples like `01-2.txt`.
+/// An example can start with a front-matter header that declares its expected answers:
+///
+/// ```text
+/// ---
+/// part_1: 3
+/// part_2: 6
+/// ---
+/// L68
+/// L30
+/// ```
+///
+/// `solution!` generates an `examples` test that checks every example with a header against the parts of its day.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::template::{Day, Year, answers, data_dir};
+
+const FRONT_MATTER_DELIMITER: &str = "---";
+
+/// Answers an example declares in its front-matter header.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Expected {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Expected {
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Split an example into its expected answers and its input.
+/// Examples without a front-matter header do not expect any answers.
+pub fn parse(contents: &str) -> Result<(Expected, &str), String> {
+    let Some(rest) = contents
+        .strip_prefix(FRONT_MATTER_DELIMITER)
+        .and_then(|rest| rest.strip_prefix('\n').or(rest.strip_prefix("\r\n")))
+    else {
+        return Ok((Expected::default(), contents));
+    };
+
+    let mut expected = Expected::default();
+    let mut offset = 0;
+
+    for line in rest.split_inclusive('\n') {
+        offset += line.len();
+        let line = line.trim_end();
+
+        if line == FRONT_MATTER_DELIMITER {
+            return Ok((expected, &rest[offset..]));
+        }
+
+        if line.is_empty() {
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once(':')
+            .ok_or_else(|| format!("expected `key: value` in front-matter, found `{line}`."))?;
+
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(format!("expected a value for `{}`.", key.trim()));
+        }
+
+        match key.trim() {
+            "part_1" => expected.part_1 = Some(value.to_string()),
+            "part_2" => expected.part_2 = Some(value.to_string()),
+            key => return Err(format!("unknown front-matter key `{key}`.")),
+        }
+    }
+
+    Err("front-matter is not closed by `---`.".into())
+}
+
+/// Prepend a front-matter header with the expected answers to an example input, see [`parse`].
+pub fn render(expected: &Expected, input: &str) -> String {
+    let answers: Vec<String> = [(1, &expected.part_1), (2, &expected.part_2)]
+        .into_iter()
+        .filter_map(|(part, answer)| Some(format!("part_{part}: {}\n", answer.as_ref()?)))
+        .collect();
+
+    if answers.is_empty() {
+        return input.to_string();
+    }
+
+    format!(
+        "{FRONT_MATTER_DELIMITER}\n{}{FRONT_MATTER_DELIMITER}\n{input}",
+        answers.concat()
+    )
+}
+
+/// The input of an example without its front-matter header.
+pub fn strip_front_matter(contents: &str) -> Result<&str, String> {
+    parse(contents).map(|(_, input)| input)
+}
+
+/// The number of an example file of a day, `1` for `01.txt` and `2` for `01-2.txt`.
+fn example_number(day: Day, path: &Path) -> Option<u64> {
+    if path.extension().is_none_or(|ext| ext != "txt") {
+        return None;
+    }
+    let stem = path.file_stem()?.to_str()?;
+    if stem == day.to_string() {
+        return Some(1);
+    }
+    let number = stem.strip_prefix(&format!("{day}-"))?;
+    if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    number.parse().ok()
+}
+
+/// The example files of a day, e.g. `01.txt`, `01-2.txt` and `01-10.txt`, sorted by their number.
+pub fn paths(year: Year, day: Day) -> Vec<PathBuf> {
+    let dir = data_dir(year).join("examples");
+
+    let mut paths: Vec<(u64, PathBuf)> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter_map(|path| Some((example_number(day, &path)?, path)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    paths.sort_unstable();
+    paths.into_iter().map(|(_, path)| path).collect()
+}
+
+/// A part of a solution that turns an input into its answer, or the error it failed with.
+pub type PartFn<'a> = &'a dyn Fn(&str) -> Result<Option<String>, String>;
+
+/// Run the parts of a day against every example that declares expected answers.
+/// Panics with a list of all mismatches, so that it can be used as a test.
+pub fn check(year: Year, day: Day, parts: &[(u8, PartFn)]) {
+    let mut checked = 0;
+    let mut failures: Vec<String> = vec![];
+
+    for path in paths(year, day) {
+        let name = path.display();
+
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) => {
+                failures.push(format!("{name}: could not read example: {e}"));
+                continue;
+            }
+        };
+
+        let (expected, input) = match parse(&contents) {
+            Ok(example) => example,
+            Err(e) => {
+                failures.push(format!("{name}: {e}"));
+                continue;
+            }
+        };
+
+        for (part, func) in parts {
+            let Some(expected) = expected.get(*part) else {
+                continue;
+            };
+
+            checked += 1;
+            match func(input) {
+                Ok(Some(answer)) if answers::matches(expected, &answer) => {
+                    println!("{name} part {part}: ok");
+                }
+                Ok(Some(answer)) => failures.push(format!(
+                    "{name} part {part}: expected `{expected}`, got `{answer}`"
+                )),
+                Ok(None) => failures.push(format!(
+                    "{name} part {part}: expected `{expected}`, got no result"
+                )),
+                Err(e) => failures.push(format!(
+                    "{name} part {part}: expected `{expected}`, got an error:\n{e}"
+                )),
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} of {checked} example answer(s) did not match:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::{Expected, example_number, parse, render};
+    use crate::day;
+
+    #[test]
+    fn parses_front_matter() {
+        let (expected, input) = parse("---\npart_1: 3\npart_2: 6\n---\nL68\nL30\n").unwrap();
+        assert_eq!(expected.part_1.as_deref(), Some("3"));
+        assert_eq!(expected.get(2), Some("6"));
+        assert_eq!(input, "L68\nL30\n");
+    }
+
+    #[test]
+    fn handles_partial_front_matter() {
+        let (expected, input) = parse("---\r\n\r\npart_2 :  abc \r\n---\r\n1\r\n").unwrap();
+        assert_eq!(expected.get(1), None);
+        assert_eq!(expected.get(2), Some("abc"));
+        assert_eq!(input, "1\r\n");
+    }
+
+    #[test]
+    fn handles_examples_without_front_matter() {
+        let (expected, input) = parse("L68\n---\n").unwrap();
+        assert_eq!(expected, Expected::default());
+        assert_eq!(input, "L68\n---\n");
+        assert_eq!(parse("----\n").unwrap().1, "----\n");
+    }
+
+    #[test]
+    fn renders_front_matter() {
+        let expected = Expected {
+            part_1: None,
+            part_2: Some("6".into()),
+        };
+        let contents = render(&expected, "L68\n");
+        assert_eq!(contents, "---\npart_2: 6\n---\nL68\n");
+        assert_eq!(parse(&contents).unwrap(), (expected, "L68\n"));
+        assert_eq!(render(&Expected::default(), "L68\n"), "L68\n");
+    }
+
+    #[test]
+    fn rejects_invalid_front_matter() {
+        assert_eq!(parse("---\npart_1: 3\nL68\n").is_err(), true);
+        assert_eq!(parse("---\npart_3: 3\n---\n").is_err(), true);
+        assert_eq!(parse("---\npart_1:\n---\n").is_err(), true);
+        assert_eq!(parse("---\npart_1 3\n---\n").is_err(), true);
+    }
+
+    #[test]
+    fn numbers_examples() {
+        let number = |path: &str| example_number(day!(1), Path::new(path));
+        assert_eq!(number("data/2025/examples/01.txt"), Some(1));
+        assert_eq!(number("data/2025/examples/01-2.txt"), Some(2));
+        assert_eq!(number("data/2025/examples/01-10.txt"), Some(10));
+        assert_eq!(number("data/2025/examples/01-.txt"), None);
+        assert_eq!(number("data/2025/examples/01-b.txt"), None);
+        assert_eq!(number("data/2025/examples/01.md"), None);
+        assert_eq!(number("data/2025/examples/11.txt"), None);
+    }
+}
@@ -0,0 +1,33 @@
+/// Module with fixtures that are shared by the tests of several modules.
+use crate::template::{Day, stats::Stats, timings::Timing};
+
+/// Statistics of a part whose samples all took `median_nanos`.
+pub fn stats(median_nanos: f64) -> Option<Stats> {
+    Some(Stats {
+        mean_nanos: median_nanos,
+        median_nanos,
+        min_nanos: median_nanos,
+        max_nanos: median_nanos,
+        stddev_nanos: 0.0,
+        ci95_nanos: 0.0,
+        samples: 10,
+        outliers: 0,
+    })
+}
+
+/// The timing of a day without any solved parts, e.g. `Timing { total_nanos: 1e9, ..timing(day!(1)) }`.
+pub fn timing(day: Day) -> Timing {
+    Timing {
+        day,
+        parse: None,
+        parse_stats: None,
+        part_1: None,
+        part_2: None,
+        part_1_stats: None,
+        part_2_stats: None,
+        part_1_memory: None,
+        part_2_memory: None,
+        total_nanos: 0.0,
+        timed_out: false,
+    }
+}
@@ -0,0 +1,402 @@
+/// Module that keeps a history of benchmark runs and compares new runs against it.
+/// Every stored run is appended as a single JSON line to the history file.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{
+    ANSI_BOLD, ANSI_RESET, Day, Year, data_dir,
+    report::PARSE_PART,
+    stats::to_duration,
+    timings::{Timing, Timings},
+};
+
+fn get_history_path(year: Year) -> PathBuf {
+    data_dir(year).join("timings_history.jsonl")
+}
+
+/// The version of the compiler the solutions were built with, see `build.rs`.
+pub const RUSTC_VERSION: &str = env!("AOC_RUSTC_VERSION");
+
+/// A single stored benchmark run.
+#[derive(Clone, Debug)]
+pub struct HistoryRun {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Short hash of the checked out git commit, if available.
+    pub commit: Option<String>,
+    pub rustc: String,
+    pub timings: Timings,
+}
+
+impl HistoryRun {
+    /// Capture a run of `timings` with the current time, git commit and rustc version.
+    pub fn capture(timings: Timings) -> Self {
+        HistoryRun {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            commit: get_commit_hash(),
+            rustc: RUSTC_VERSION.into(),
+            timings,
+        }
+    }
+}
+
+/// All stored benchmark runs, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub runs: Vec<HistoryRun>,
+}
+
+impl History {
+    /// Read the history file of a year. If not present, returns an empty history.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        match fs::read_to_string(get_history_path(year)) {
+            Ok(contents) => History::from_str(&contents),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Append a run to the history file of a year.
+    pub fn append_to_file(year: Year, run: &HistoryRun) -> Result<(), io::Error> {
+        let path = get_history_path(year);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let line = JsonValue::from(run)
+            .stringify()
+            .map_err(|e| io::Error::other(e.to_string()))?;
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{line}")
+    }
+
+    /// The most recent stored timing for a day.
+    pub fn last_timing(&self, day: Day) -> Option<&Timing> {
+        self.runs
+            .iter()
+            .rev()
+            .find_map(|run| run.timings.data.iter().find(|t| t.day == day))
+    }
+
+    /// Compare `timings` part by part against the most recent stored timing of each day.
+    /// Parts without statistics on either side are skipped.
+    pub fn compare(&self, timings: &Timings) -> Vec<Comparison> {
+        let mut comparisons = vec![];
+
+        for current in &timings.data {
+            let Some(previous) = self.last_timing(current.day) else {
+                continue;
+            };
+
+            let parts = [
+                (PARSE_PART, &previous.parse_stats, &current.parse_stats),
+                (1, &previous.part_1_stats, &current.part_1_stats),
+                (2, &previous.part_2_stats, &current.part_2_stats),
+            ];
+
+            for (part, previous_stats, current_stats) in parts {
+                if let (Some(previous_stats), Some(current_stats)) = (previous_stats, current_stats)
+                {
+                    comparisons.push(Comparison {
+                        day: current.day,
+                        part,
+                        previous_nanos: previous_stats.median_nanos,
+                        current_nanos: current_stats.median_nanos,
+                    });
+                }
+            }
+        }
+
+        comparisons
+    }
+}
+
+/// The change of a part's median time between the last stored run and the current run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub day: Day,
+    /// 1 or 2, or [`PARSE_PART`] for the shared parse step.
+    pub part: u8,
+    pub previous_nanos: f64,
+    pub current_nanos: f64,
+}
+
+impl Comparison {
+    /// Relative change in percent, positive values are slowdowns.
+    /// A part that previously took no measurable time has no relative change and yields 0.
+    pub fn change_percent(&self) -> f64 {
+        if self.previous_nanos <= 0.0 {
+            return 0.0;
+        }
+        (self.current_nanos - self.previous_nanos) / self.previous_nanos * 100.0
+    }
+
+    /// Whether the part got slower by more than `threshold` percent.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change_percent() > threshold
+    }
+}
+
+/// Print comparisons as a list, returns the number of regressions beyond `threshold` percent.
+pub fn print_comparisons(comparisons: &[Comparison], threshold: f64) -> usize {
+    println!("{ANSI_BOLD}Comparison with last stored run{ANSI_RESET}");
+    println!("------");
+
+    if comparisons.is_empty() {
+        println!("No stored runs to compare against.");
+        return 0;
+    }
+
+    for comparison in comparisons {
+        let change = comparison.change_percent();
+        let verdict = if comparison.is_regression(threshold) {
+            "regression"
+        } else if change < -threshold {
+            "speedup"
+        } else {
+            "unchanged"
+        };
+        let part = match comparison.part {
+            PARSE_PART => "Parse".into(),
+            part => format!("Part {part}"),
+        };
+        println!(
+            "Day {} {part}: {:.1?} -> {:.1?} ({change:+.1}%, {verdict})",
+            comparison.day,
+            to_duration(comparison.previous_nanos),
+            to_duration(comparison.current_nanos),
+        );
+    }
+
+    comparisons
+        .iter()
+        .filter(|c| c.is_regression(threshold))
+        .count()
+}
+
+fn get_commit_hash() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for History {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let runs = s
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let json = JsonValue::from_str(line).or(Err("not valid JSON line."))?;
+                HistoryRun::try_from(&json)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(History { runs })
+    }
+}
+
+impl From<&HistoryRun> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &HistoryRun) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            match &value.commit {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("rustc".into(), JsonValue::String(value.rustc.clone()));
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.timings.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryRun {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history run to be a JSON object.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected run.timestamp to be a number.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected run.commit to be null or string.")?;
+
+        let rustc = json
+            .get("rustc")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected run.rustc to be a string.")?;
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected run.data to be an array.")?
+            .iter()
+            .map(Timing::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(HistoryRun {
+            timestamp,
+            commit: commit.cloned(),
+            rustc: rustc.clone(),
+            timings: Timings {
+                data,
+                ..Timings::default()
+            },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+
+    use tinyjson::JsonValue;
+
+    use super::{Comparison, History, HistoryRun};
+    use crate::{
+        day,
+        template::{
+            Day,
+            fixtures::{self, stats},
+            timings::{Timing, Timings},
+        },
+    };
+
+    fn timing(day: Day, part_1: f64, part_2: Option<f64>) -> Timing {
+        Timing {
+            part_1: Some("-".into()),
+            part_2: part_2.map(|_| "-".into()),
+            part_1_stats: stats(part_1),
+            part_2_stats: part_2.and_then(stats),
+            total_nanos: part_1 + part_2.unwrap_or_default(),
+            ..fixtures::timing(day)
+        }
+    }
+
+    fn run(timestamp: u64, data: Vec<Timing>) -> HistoryRun {
+        HistoryRun {
+            timestamp,
+            commit: Some("abc1234".into()),
+            rustc: "rustc 1.0.0".into(),
+            timings: Timings {
+                data,
+                ..Timings::default()
+            },
+        }
+    }
+
+    fn get_mock_history() -> History {
+        History {
+            runs: vec![
+                run(1, vec![timing(day!(1), 100.0, Some(200.0))]),
+                run(2, vec![timing(day!(2), 1000.0, None)]),
+                run(3, vec![timing(day!(1), 110.0, Some(250.0))]),
+            ],
+        }
+    }
+
+    #[test]
+    fn roundtrips_json_lines() {
+        let history = get_mock_history();
+        let lines: Vec<String> = history
+            .runs
+            .iter()
+            .map(|run| JsonValue::from(run).stringify().unwrap())
+            .collect();
+        let parsed = History::from_str(&lines.join("\n")).unwrap();
+        assert_eq!(parsed.runs.len(), 3);
+        assert_eq!(parsed.runs[2].timestamp, 3);
+        assert_eq!(parsed.runs[2].commit, Some("abc1234".into()));
+        assert_eq!(parsed.runs[2].rustc, "rustc 1.0.0");
+        assert_eq!(parsed.runs[2].timings.data[0].total_nanos, 360.0);
+    }
+
+    #[test]
+    fn finds_last_timing_per_day() {
+        let history = get_mock_history();
+        assert_eq!(history.last_timing(day!(1)).unwrap().total_nanos, 360.0);
+        assert_eq!(history.last_timing(day!(2)).unwrap().total_nanos, 1000.0);
+        assert_eq!(history.last_timing(day!(3)).is_none(), true);
+    }
+
+    #[test]
+    fn compares_against_last_run() {
+        let history = get_mock_history();
+        let current = Timings {
+            data: vec![
+                timing(day!(1), 99.0, Some(300.0)),
+                timing(day!(2), 500.0, Some(10.0)),
+                timing(day!(3), 1.0, None),
+            ],
+            ..Timings::default()
+        };
+        let comparisons = history.compare(&current);
+        assert_eq!(comparisons.len(), 3);
+        assert_eq!(comparisons[0].day, day!(1));
+        assert_eq!(comparisons[0].change_percent(), -10.0);
+        assert_eq!(comparisons[0].is_regression(5.0), false);
+        assert_eq!(comparisons[1].part, 2);
+        assert_eq!(comparisons[1].change_percent(), 20.0);
+        assert_eq!(comparisons[1].is_regression(5.0), true);
+        assert_eq!(comparisons[1].is_regression(25.0), false);
+        assert_eq!(comparisons[2].day, day!(2));
+        assert_eq!(comparisons[2].change_percent(), -50.0);
+    }
+
+    #[test]
+    fn compares_against_a_run_without_measurable_time() {
+        let comparison = Comparison {
+            day: day!(1),
+            part: 1,
+            previous_nanos: 0.0,
+            current_nanos: 100.0,
+        };
+        assert_eq!(comparison.change_percent(), 0.0);
+        assert_eq!(comparison.is_regression(5.0), false);
+    }
+}
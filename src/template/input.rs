@@ -0,0 +1,205 @@
+/// Module that selects the input a solution binary runs against.
+/// By default this is the puzzle input, `solve` can pass `--input <path>`, `--example[=N]` or `--stdin` instead.
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::PathBuf,
+    process,
+};
+
+use crate::template::{Day, Year, data_dir, examples};
+
+/// Where a solution binary reads its input from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input, e.g. `data/2025/inputs/01.txt`.
+    #[default]
+    Puzzle,
+    /// The example, e.g. `data/2025/examples/01.txt`, or a numbered example like `01-2.txt`.
+    /// The first example is `01.txt`, so `Some(1)` reads the same file as `None` and as `read_file_part("examples", .., 1)`.
+    Example(Option<u8>),
+    /// An arbitrary file, e.g. the input of a teammate.
+    File(PathBuf),
+    /// Everything that is piped to the binary.
+    Stdin,
+}
+
+impl InputSource {
+    /// Parse the arguments passed to a solution binary, see [`InputSource::to_args`].
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        match Self::parse(&args) {
+            Ok(source) => source,
+            Err(e) => {
+                eprintln!("Unexpected command-line input. {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let position = |flag: &str| args.iter().position(|x| x == flag);
+
+        let mut sources = vec![];
+
+        if let Some(index) = position("--input") {
+            match args.get(index + 1) {
+                Some(path) if !path.starts_with("--") => {
+                    sources.push(InputSource::File(path.into()));
+                }
+                _ => return Err("Format: --input <path>".into()),
+            }
+        }
+
+        if position("--example").is_some() {
+            sources.push(InputSource::Example(None));
+        }
+        if let Some(number) = args.iter().find_map(|x| x.strip_prefix("--example=")) {
+            match number.parse() {
+                Ok(number) if number > 0 => sources.push(InputSource::Example(Some(number))),
+                _ => return Err("Format: --example=<number>".into()),
+            }
+        }
+
+        if position("--stdin").is_some() {
+            sources.push(InputSource::Stdin);
+        }
+
+        match sources.len() {
+            0 => Ok(InputSource::Puzzle),
+            1 => Ok(sources.remove(0)),
+            _ => Err("Only one of --input, --example and --stdin can be passed.".into()),
+        }
+    }
+
+    /// Arguments that select this input in a solution binary, see [`InputSource::from_args`].
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Puzzle => vec![],
+            InputSource::Example(None) => vec!["--example".into()],
+            InputSource::Example(Some(number)) => vec![format!("--example={number}")],
+            InputSource::File(path) => vec!["--input".into(), path.display().to_string()],
+            InputSource::Stdin => vec!["--stdin".into()],
+        }
+    }
+
+    /// The file the input is read from, `None` for stdin.
+    pub fn path(&self, year: Year, day: Day) -> Option<PathBuf> {
+        match self {
+            InputSource::Puzzle => Some(data_dir(year).join("inputs").join(format!("{day}.txt"))),
+            InputSource::Example(None | Some(1)) => {
+                Some(data_dir(year).join("examples").join(format!("{day}.txt")))
+            }
+            InputSource::Example(Some(number)) => Some(
+                data_dir(year)
+                    .join("examples")
+                    .join(format!("{day}-{number}.txt")),
+            ),
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+        }
+    }
+
+    /// Read the input of a day, examples are read without their front-matter header.
+    pub fn read(&self, year: Year, day: Day) -> Result<String, io::Error> {
+        match self.path(year, day) {
+            Some(path) if matches!(self, InputSource::Example(_)) => {
+                let contents = fs::read_to_string(path)?;
+                examples::strip_front_matter(&contents)
+                    .map(str::to_string)
+                    .map_err(io::Error::other)
+            }
+            Some(path) => fs::read_to_string(path),
+            None => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+
+    /// Read the input of a day, exiting with an error message if that fails.
+    pub fn read_or_exit(&self, year: Year, day: Day) -> String {
+        self.read(year, day).unwrap_or_else(|e| {
+            match self.path(year, day) {
+                Some(path) => eprintln!("Could not read input file \"{}\": {e}", path.display()),
+                None => eprintln!("Could not read input from stdin: {e}"),
+            }
+            process::exit(1);
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::InputSource;
+    use crate::{day, year};
+
+    fn parse(args: &str) -> Result<InputSource, String> {
+        let args: Vec<String> = args.split_whitespace().map(String::from).collect();
+        InputSource::parse(&args)
+    }
+
+    #[test]
+    fn parses_sources() {
+        assert_eq!(parse("01 --time"), Ok(InputSource::Puzzle));
+        assert_eq!(parse("01 --example"), Ok(InputSource::Example(None)));
+        assert_eq!(parse("01 --example=2"), Ok(InputSource::Example(Some(2))));
+        assert_eq!(
+            parse("01 --example --submit 1"),
+            Ok(InputSource::Example(None))
+        );
+        assert_eq!(
+            parse("01 --input other.txt"),
+            Ok(InputSource::File("other.txt".into()))
+        );
+        assert_eq!(parse("01 --stdin"), Ok(InputSource::Stdin));
+    }
+
+    #[test]
+    fn rejects_invalid_sources() {
+        assert_eq!(parse("01 --input").is_err(), true);
+        assert_eq!(parse("01 --input --time").is_err(), true);
+        assert_eq!(parse("01 --stdin --example").is_err(), true);
+        assert_eq!(parse("01 --example=0").is_err(), true);
+        assert_eq!(parse("01 --example=two").is_err(), true);
+    }
+
+    #[test]
+    fn roundtrips_args() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::Example(None),
+            InputSource::Example(Some(3)),
+            InputSource::File("a b.txt".into()),
+            InputSource::Stdin,
+        ] {
+            let mut args = vec!["01".to_string()];
+            args.extend(source.to_args());
+            assert_eq!(InputSource::parse(&args), Ok(source));
+        }
+    }
+
+    #[test]
+    fn resolves_paths() {
+        let path = |source: InputSource| source.path(year!(2025), day!(1));
+        assert_eq!(
+            path(InputSource::Puzzle),
+            Some(PathBuf::from("data/2025/inputs/01.txt"))
+        );
+        assert_eq!(
+            path(InputSource::Example(Some(1))),
+            Some(PathBuf::from("data/2025/examples/01.txt"))
+        );
+        assert_eq!(
+            path(InputSource::Example(Some(2))),
+            Some(PathBuf::from("data/2025/examples/01-2.txt"))
+        );
+        assert_eq!(path(InputSource::Stdin), None);
+    }
+}
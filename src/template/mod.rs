@@ -1,68 +1,209 @@
-use std::{env, fs};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
 
-pub mod aoc_cli;
+pub mod alloc;
+pub mod answer;
+pub mod aoc_client;
+pub mod benchmark_report;
 pub mod commands;
+pub mod config;
+pub mod examples;
+pub mod input;
+pub mod ocr;
+pub mod report;
 pub mod runner;
+pub mod selection;
+pub mod timeouts;
 
 pub use day::*;
+pub use year::*;
 
+mod answers;
 mod day;
+mod environment;
+#[cfg(feature = "test_lib")]
+mod fixtures;
+mod history;
+mod module_templates;
+mod puzzle_examples;
 mod readme_benchmarks;
 mod run_multi;
+mod stats;
+mod submissions;
 mod timings;
+mod watch;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// The data folder of a year, e.g. `data/2025`, see [`config`].
+#[must_use]
+pub fn data_dir(year: Year) -> PathBuf {
+    config::get().data_dir.join(year.to_string())
+}
+
+/// The folder of the solution binaries.
+/// Not configurable, cargo and `build.rs` only discover binaries in `src/bin`.
+pub const BIN_DIR: &str = "src/bin";
+
+/// The name of the solution binary of a day, e.g. `2025-01` for `src/bin/2025-01.rs`.
+#[must_use]
+pub fn bin_name(year: Year, day: Day) -> String {
+    format!("{year}-{day}")
+}
+
+/// The module of a day, e.g. `src/bin/2025-01.rs`.
+#[must_use]
+pub fn bin_path(year: Year, day: Day) -> PathBuf {
+    Path::new(BIN_DIR).join(format!("{}.rs", bin_name(year, day)))
+}
+
 /// Helper function that reads a text file to a string.
+/// The front-matter header of examples is stripped, see [`examples`].
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, year: Year, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd
+        .join(data_dir(year))
+        .join(folder)
+        .join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    strip_example(folder, f.expect("could not open input file"))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+/// Examples are numbered like `--example=N`, the first example is `01.txt` rather than `01-1.txt`, see [`examples`].
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, year: Year, day: Day, part: u8) -> String {
+    let name = if folder == "examples" && part == 1 {
+        format!("{day}.txt")
+    } else {
+        format!("{day}-{part}.txt")
+    };
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let filepath = cwd.join(data_dir(year)).join(folder).join(name);
     let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    strip_example(folder, f.expect("could not open input file"))
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+fn strip_example(folder: &str, contents: String) -> String {
+    if folder != "examples" {
+        return contents;
+    }
+    match examples::strip_front_matter(&contents) {
+        Ok(input) => input.to_string(),
+        Err(e) => panic!("invalid example: {e}"),
+    }
+}
+
+/// Creates the constants `YEAR` and `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The year is passed as `year = 2024`, solutions without a year belong to [`Year::DEFAULT`].
+///
+/// With `parse = parse_input`, the input is parsed once and a reference to the parsed value is passed to the parts.
+/// The parse step is timed separately from the parts.
+///
+/// Also generates an `examples` test that checks the parts against the expected answers of the examples of the day.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:expr $(, year = $year:expr)? $(, parse = $parse:expr)?) => {
+        $crate::solution!(@impl $crate::solution!(@year $($year)?), $day, [$($parse)?], [part_one, 1] [part_two, 2]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($day:expr, 1 $(, year = $year:expr)? $(, parse = $parse:expr)?) => {
+        $crate::solution!(@impl $crate::solution!(@year $($year)?), $day, [$($parse)?], [part_one, 1]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($day:expr, 2 $(, year = $year:expr)? $(, parse = $parse:expr)?) => {
+        $crate::solution!(@impl $crate::solution!(@year $($year)?), $day, [$($parse)?], [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@year) => {
+        $crate::template::Year::DEFAULT
+    };
+    (@year $year:expr) => {
+        $crate::year!($year)
+    };
+
+    (@impl $year:expr, $day:expr, [], $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@common $year, $day);
+
+        /// The parts of the solution, in the order they run.
+        pub const PARTS: &[u8] = &[$( $part ),*];
+
+        /// Runs every part of the solution against `input`.
+        pub fn run_parts(
+            input: &str,
+            options: &$crate::template::runner::RunOptions,
+        ) -> Vec<$crate::template::report::PartReport> {
+            use $crate::template::runner::*;
+            vec![$( run_part($func, input, YEAR, DAY, $part, options), )*]
+        }
+
+        /// Checks the parts against the expected answers of every example, see [`examples`](`$crate::template::examples`).
+        #[cfg(test)]
+        #[test]
+        fn examples() {
+            $crate::template::examples::check(YEAR, DAY, &[
+                $( ($part, &|input: &str| $crate::template::answer::PartResult::to_outcome(&$func(input)).map(|answer| answer.map(|answer| answer.text()))), )*
+            ]);
+        }
+    };
+    (@impl $year:expr, $day:expr, [$parse:expr], $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@common $year, $day);
+
+        /// The parts of the solution in the order they run, starting with the parse step.
+        pub const PARTS: &[u8] = &[$crate::template::report::PARSE_PART, $( $part ),*];
+
+        /// Parses `input` once and runs every part of the solution against the parsed value.
+        pub fn run_parts(
+            input: &str,
+            options: &$crate::template::runner::RunOptions,
+        ) -> Vec<$crate::template::report::PartReport> {
+            use $crate::template::runner::*;
+            let (parsed, parse_report) = run_parse($parse, input, DAY, options);
+            let mut reports = vec![parse_report];
+            if let Some(parsed) = &parsed {
+                $( reports.push(run_part(|parsed| $func(parsed), parsed, YEAR, DAY, $part, options)); )*
+            }
+            reports
+        }
+
+        /// Checks the parts against the expected answers of every example, see [`examples`](`$crate::template::examples`).
+        #[cfg(test)]
+        #[test]
+        fn examples() {
+            $crate::template::examples::check(YEAR, DAY, &[
+                $( ($part, &|input: &str| $crate::template::answer::PartResult::to_outcome(&$func(&$parse(input))).map(|answer| answer.map(|answer| answer.text()))), )*
+            ]);
+        }
+    };
+
+    (@common $year:expr, $day:expr) => {
+        /// The year of the puzzle.
+        pub const YEAR: $crate::template::Year = $year;
+
         /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+        pub const DAY: $crate::template::Day = $crate::day!($day);
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            use $crate::template::{input::InputSource, report, runner::*};
+            report::declare_parts(DAY, PARTS);
+            let source = InputSource::from_args();
+            let options = RunOptions::from_args();
+            if options.submit.is_some() && source != InputSource::Puzzle {
+                eprintln!("Only results for the puzzle input can be submitted.");
+                std::process::exit(1);
+            }
+            let input = source.read_or_exit(YEAR, DAY);
+            run_parts(&input, &options);
         }
     };
 }
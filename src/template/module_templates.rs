@@ -0,0 +1,172 @@
+/// Module that resolves and renders the templates `scaffold` creates solution modules from.
+/// Templates are looked up by name in the template directory of `aoc.toml` (default: `templates/`) first, e.g. `templates/grid.txt`,
+/// so that the built-in templates can be overridden and extended.
+use std::{error::Error, fmt::Display, fs, io, path::Path};
+
+use crate::template::{Day, Year, config};
+
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+const BUILT_IN: &[(&str, &str)] = &[
+    (
+        "default",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/default.txt"
+        )),
+    ),
+    (
+        "grid",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/grid.txt"
+        )),
+    ),
+    (
+        "graph",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/graph.txt"
+        )),
+    ),
+];
+
+#[derive(Debug)]
+pub enum TemplateError {
+    /// Neither the template directory nor the built-in templates contain a template of this name.
+    Unknown(String),
+    IO(io::Error),
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::Unknown(name) => write!(
+                f,
+                "unknown template `{name}`, expecting a file \"{}\" or one of: {}",
+                config::get()
+                    .template_dir
+                    .join(format!("{name}.txt"))
+                    .display(),
+                BUILT_IN
+                    .iter()
+                    .map(|(name, _)| *name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            TemplateError::IO(e) => write!(f, "could not read template: {e}"),
+        }
+    }
+}
+
+impl Error for TemplateError {}
+
+impl From<io::Error> for TemplateError {
+    fn from(e: io::Error) -> Self {
+        TemplateError::IO(e)
+    }
+}
+
+/// Load a template by name, preferring a user-defined template over a built-in one.
+pub fn load(name: &str) -> Result<String, TemplateError> {
+    load_from(&config::get().template_dir, name)
+}
+
+fn load_from(dir: &Path, name: &str) -> Result<String, TemplateError> {
+    // NOTE: names are plain file stems, so that a template cannot point outside of the template directory.
+    if name.is_empty() || name.contains(['/', '\\', '.']) {
+        return Err(TemplateError::Unknown(name.to_string()));
+    }
+
+    match fs::read_to_string(dir.join(format!("{name}.txt"))) {
+        Ok(template) => Ok(template),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => BUILT_IN
+            .iter()
+            .find(|(built_in, _)| *built_in == name)
+            .map(|(_, template)| (*template).to_string())
+            .ok_or_else(|| TemplateError::Unknown(name.to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// The title of a puzzle from its markdown, e.g. `Day 1: Secret Entrance`.
+pub fn puzzle_title(markdown: &str) -> Option<String> {
+    let heading = markdown.lines().find(|line| line.starts_with("## "))?;
+    let title = heading
+        .trim_start_matches("## ")
+        .trim_matches(|c: char| c == '-' || c.is_whitespace());
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// Fill in the placeholders of a template:
+/// `%YEAR%`, `%DAY_NUMBER%` (e.g. `1`), `%DAY%` (e.g. `01`), `%TITLE%` and `%URL%` of the puzzle.
+/// Without a title, e.g. if the puzzle has not been downloaded yet, `%TITLE%` is `Day <n>`.
+pub fn render(template: &str, year: Year, day: Day, title: Option<&str>) -> String {
+    let day_number = day.into_inner().to_string();
+    let title = title.map_or_else(|| format!("Day {day_number}"), str::to_string);
+
+    template
+        .replace("%YEAR%", &year.to_string())
+        .replace("%DAY_NUMBER%", &day_number)
+        .replace("%DAY%", &day.to_string())
+        .replace("%TITLE%", &title)
+        .replace(
+            "%URL%",
+            &format!("https://adventofcode.com/{year}/day/{day_number}"),
+        )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs};
+
+    use super::{TemplateError, load_from, puzzle_title, render};
+    use crate::{day, year};
+
+    #[test]
+    fn renders_placeholders() {
+        let template = "//! [%TITLE%](%URL%)\nsolution!(%DAY_NUMBER%, year = %YEAR%); // %DAY%.txt";
+        assert_eq!(
+            render(template, year!(2024), day!(5), Some("Day 5: Print Queue")),
+            "//! [Day 5: Print Queue](https://adventofcode.com/2024/day/5)\nsolution!(5, year = 2024); // 05.txt"
+        );
+        assert_eq!(
+            render("%TITLE%", year!(2024), day!(5), None),
+            "Day 5".to_string()
+        );
+    }
+
+    #[test]
+    fn parses_puzzle_titles() {
+        let markdown = "## --- Day 1: Secret Entrance ---\n\nText\n\n## --- Part Two ---\n";
+        assert_eq!(
+            puzzle_title(markdown),
+            Some("Day 1: Secret Entrance".to_string())
+        );
+        assert_eq!(puzzle_title("No heading"), None);
+    }
+
+    #[test]
+    fn prefers_user_templates() {
+        let dir = env::temp_dir().join(format!("aoc-templates-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("grid.txt"), "custom grid").unwrap();
+        fs::write(dir.join("mine.txt"), "mine").unwrap();
+
+        assert_eq!(load_from(&dir, "grid").unwrap(), "custom grid");
+        assert_eq!(load_from(&dir, "mine").unwrap(), "mine");
+        assert_eq!(
+            load_from(&dir, "graph").unwrap().contains("parse_graph"),
+            true
+        );
+        assert_eq!(
+            matches!(load_from(&dir, "nope"), Err(TemplateError::Unknown(_))),
+            true
+        );
+        assert_eq!(load_from(&dir, "../grid").is_err(), true);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
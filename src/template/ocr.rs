@@ -0,0 +1,202 @@
+/// Module that reads letters drawn on a grid, e.g. by a part that simulates a display.
+/// Supports the two fonts of the puzzles: letters of 4x6 cells, and large letters of 6x10 cells.
+/// Letters are spaced evenly, one empty column after each small letter and two after each large letter.
+use std::collections::HashMap;
+
+/// A font of glyphs of the same size, every glyph is followed by `spacing` empty columns.
+struct Font {
+    width: usize,
+    height: usize,
+    spacing: usize,
+    glyphs: &'static [(char, &'static [&'static str])],
+}
+
+#[rustfmt::skip]
+const SMALL_FONT: Font = Font {
+    width: 4,
+    height: 6,
+    spacing: 1,
+    glyphs: &[
+        ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+        ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+        ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+        ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+        ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+        ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+        ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+        ('I', &[".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+        ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+        ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+        ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+        ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+        ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+        ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+        ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        // NOTE: the right arm of `Y` reaches into the spacing column, which is not part of the glyph.
+        ('Y', &["#...", "#...", ".#.#", "..#.", "..#.", "..#."]),
+        ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+    ],
+};
+
+#[rustfmt::skip]
+const LARGE_FONT: Font = Font {
+    width: 6,
+    height: 10,
+    spacing: 2,
+    glyphs: &[
+        ('A', &["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+        ('B', &["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+        ('C', &[".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+        ('E', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+        ('F', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+        ('G', &[".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+        ('H', &["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+        ('J', &["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+        ('K', &["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+        ('L', &["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+        ('N', &["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+        ('P', &["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+        ('R', &["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+        ('X', &["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+        ('Z', &["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+    ],
+};
+
+impl Font {
+    fn glyphs(&self) -> HashMap<Vec<bool>, char> {
+        self.glyphs
+            .iter()
+            .map(|(letter, rows)| {
+                let cells = rows.iter().flat_map(|row| row.bytes().map(|b| b == b'#'));
+                (cells.collect(), *letter)
+            })
+            .collect()
+    }
+}
+
+/// Read the letters drawn by the lit cells of a grid, `None` if the grid contains anything but letters.
+/// Empty rows and columns around the letters are ignored.
+pub fn recognize(grid: &[Vec<bool>]) -> Option<String> {
+    let lit_rows: Vec<usize> = (0..grid.len())
+        .filter(|&y| grid[y].contains(&true))
+        .collect();
+    let (&top, &bottom) = (lit_rows.first()?, lit_rows.last()?);
+
+    let lit = |y: usize, x: usize| grid[y].get(x).copied().unwrap_or(false);
+    let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+    let left = (0..width).find(|&x| (top..=bottom).any(|y| lit(y, x)))?;
+    let right = (0..width)
+        .rev()
+        .find(|&x| (top..=bottom).any(|y| lit(y, x)))?;
+
+    let font = [SMALL_FONT, LARGE_FONT]
+        .into_iter()
+        .find(|font| font.height == bottom - top + 1)?;
+    let glyphs = font.glyphs();
+
+    let stride = font.width + font.spacing;
+    let read = |left: usize| -> Option<String> {
+        (0..=(right - left) / stride)
+            .map(|i| {
+                let x = left + i * stride;
+                let cells: Vec<bool> = (top..=bottom)
+                    .flat_map(|y| (x..x + font.width).map(move |x| lit(y, x)))
+                    .collect();
+
+                // the spacing after a letter has to be empty, except for the arm of a small `Y`.
+                let spaced = (top..=bottom).all(|y| {
+                    (x + font.width..x + stride)
+                        .skip(usize::from(font.spacing == 1 && y - top < 2))
+                        .all(|x| !lit(y, x))
+                });
+
+                glyphs.get(&cells).copied().filter(|_| spaced)
+            })
+            .collect()
+    };
+
+    // NOTE: some letters, e.g. a small `I`, start with an empty column, so the first letter may start before `left`.
+    (left.saturating_sub(font.width - 1)..=left).find_map(read)
+}
+
+/// Read the letters of a grid of characters, where `#` and `█` are lit cells, see [`recognize`].
+pub fn recognize_chars(grid: &[Vec<char>]) -> Option<String> {
+    let grid: Vec<Vec<bool>> = grid
+        .iter()
+        .map(|row| row.iter().map(|c| matches!(c, '#' | '█')).collect())
+        .collect();
+    recognize(&grid)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{LARGE_FONT, SMALL_FONT, recognize_chars};
+
+    fn grid(rows: &[&str]) -> Vec<Vec<char>> {
+        rows.iter().map(|row| row.chars().collect()).collect()
+    }
+
+    #[test]
+    fn recognizes_small_letters() {
+        let rows = [
+            "####.###..#..#.####.####.#..#..##..####.",
+            "#....#..#.#..#....#.#....#..#.#..#....#.",
+            "###..#..#.####...#..###..####.#......#..",
+            "#....###..#..#..#...#....#..#.#.....#...",
+            "#....#....#..#.#....#....#..#.#..#.#....",
+            "####.#....#..#.####.#....#..#..##..####.",
+        ];
+        assert_eq!(recognize_chars(&grid(&rows)), Some("EPHZFHCZ".into()));
+    }
+
+    #[test]
+    fn recognizes_large_letters() {
+        let rows = [
+            "..................",
+            "..##....#####.....",
+            ".#..#...#....#....",
+            "#....#..#....#....",
+            "#....#..#....#....",
+            "#....#..#####.....",
+            "######..#....#....",
+            "#....#..#....#....",
+            "#....#..#....#....",
+            "#....#..#....#....",
+            "#....#..#####.....",
+        ];
+        assert_eq!(recognize_chars(&grid(&rows)), Some("AB".into()));
+    }
+
+    #[test]
+    fn recognizes_every_glyph() {
+        for font in [SMALL_FONT, LARGE_FONT] {
+            for (letter, rows) in font.glyphs {
+                let rows: Vec<Vec<char>> = rows.iter().map(|row| row.chars().collect()).collect();
+                assert_eq!(recognize_chars(&rows), Some(letter.to_string()));
+            }
+        }
+    }
+
+    #[test]
+    fn handles_small_y() {
+        let rows = [
+            "#...####.",
+            "#...##..#",
+            ".#.#.#..#",
+            "..#..###.",
+            "..#..#...",
+            "..#..#...",
+        ];
+        assert_eq!(recognize_chars(&grid(&rows)), Some("YP".into()));
+    }
+
+    #[test]
+    fn rejects_unknown_drawings() {
+        assert_eq!(recognize_chars(&grid(&["#"; 6])), None);
+        assert_eq!(recognize_chars(&grid(&["#.#.#"; 3])), None);
+        assert_eq!(recognize_chars(&grid(&["....", "...."])), None);
+    }
+}
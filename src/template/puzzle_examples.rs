@@ -0,0 +1,222 @@
+/// Module that extracts examples and their expected answers from the markdown of a puzzle, see `download`.
+/// The heuristics follow the usual structure of a puzzle description:
+/// - the example of a part is the first code block with more than one line in its section.
+/// - the expected answer is the last emphasized code span of the section, e.g. `` `*3*` ``.
+/// - a part without an example of its own reuses the example of part one.
+use regex::Regex;
+
+use crate::template::examples::Expected;
+
+/// An example input found in a puzzle, together with the answers it is expected to produce.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FoundExample {
+    pub input: String,
+    pub expected: Expected,
+}
+
+/// The example input and answer of a single part.
+#[derive(Debug, Default)]
+struct Section {
+    example: Option<String>,
+    answer: Option<String>,
+}
+
+/// Split the markdown into the sections of each part, every `## ` heading starts a new part.
+fn sections(markdown: &str) -> Vec<Section> {
+    let answer_pattern = Regex::new(r"`\*([^`*]+)\*`|\*`([^`*]+)`\*").unwrap();
+
+    let mut sections: Vec<Section> = vec![];
+    let mut block: Option<Vec<&str>> = None;
+
+    for line in markdown.lines() {
+        if let Some(lines) = &mut block {
+            if line.trim_end() == "```" {
+                let is_example = lines.len() > 1;
+                let lines = block.take().unwrap_or_default();
+                if let Some(section) = sections.last_mut()
+                    && section.example.is_none()
+                    && is_example
+                {
+                    section.example = Some(lines.join("\n") + "\n");
+                }
+            } else {
+                lines.push(line);
+            }
+            continue;
+        }
+
+        if line.starts_with("## ") || sections.is_empty() {
+            sections.push(Section::default());
+        }
+
+        if line.trim_end() == "```" {
+            block = Some(vec![]);
+            continue;
+        }
+
+        if let Some(section) = sections.last_mut()
+            && let Some(captures) = answer_pattern.captures_iter(line).last()
+        {
+            let answer = captures.get(1).or(captures.get(2));
+            section.answer = answer.map(|answer| answer.as_str().trim().to_string());
+        }
+    }
+
+    sections
+}
+
+/// Find the examples of a puzzle, at most one per part.
+pub fn analyze(markdown: &str) -> Vec<FoundExample> {
+    let mut sections = sections(markdown).into_iter();
+    let part_1 = sections.next().unwrap_or_default();
+    let part_2 = sections.next().unwrap_or_default();
+
+    let mut found: Vec<FoundExample> = vec![];
+
+    if let Some(input) = part_1.example.clone() {
+        found.push(FoundExample {
+            input,
+            expected: Expected {
+                part_1: part_1.answer,
+                part_2: None,
+            },
+        });
+    }
+
+    match (part_2.example, found.first_mut()) {
+        // the second part usually continues with the example of the first part.
+        (None, Some(first)) => first.expected.part_2 = part_2.answer,
+        (Some(input), Some(first)) if input == first.input => {
+            first.expected.part_2 = part_2.answer;
+        }
+        (Some(input), _) => found.push(FoundExample {
+            input,
+            expected: Expected {
+                part_1: None,
+                part_2: part_2.answer,
+            },
+        }),
+        (None, None) => {}
+    }
+
+    found
+}
+
+/// Fill in the expected value of a scaffolded test, e.g. `test_part_one`, if it still expects `None`.
+/// With `example`, the test is changed to read that numbered example, e.g. `01-2.txt`.
+/// Returns `None` if the test was not found, does not expect `None` or the answer is not a number.
+pub fn update_test(source: &str, test: &str, answer: &str, example: Option<u8>) -> Option<String> {
+    answer.parse::<i64>().ok()?;
+
+    let start = source.find(&format!("fn {test}()"))?;
+    let end = source[start + 1..]
+        .find("fn ")
+        .map_or(source.len(), |end| start + 1 + end);
+    let body = &source[start..end];
+
+    let assertion = "assert_eq!(result, None);";
+    if !body.contains(assertion) {
+        return None;
+    }
+
+    let mut updated = body.replacen(
+        assertion,
+        &format!("assert_eq!(result, Some({answer}));"),
+        1,
+    );
+    if let Some(example) = example {
+        updated = updated.replacen(
+            "read_file(\"examples\", YEAR, DAY)",
+            &format!("read_file_part(\"examples\", YEAR, DAY, {example})"),
+            1,
+        );
+    }
+
+    Some(format!("{}{updated}{}", &source[..start], &source[end..]))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{analyze, update_test};
+
+    const PUZZLE: &str = "## --- Day 1: Test ---
+
+For example, with `3` steps:
+
+```
+L68
+R30
+```
+
+A single line is not an example:
+
+```
+R5
+```
+
+The dial points at zero `*3*` times, not `*1*`... actually `*3*` times.
+
+## --- Part Two ---
+
+Now count *every* click, which is *`6`* in the example.
+";
+
+    #[test]
+    fn finds_shared_example() {
+        let found = analyze(PUZZLE);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].input, "L68\nR30\n");
+        assert_eq!(found[0].expected.part_1.as_deref(), Some("3"));
+        assert_eq!(found[0].expected.part_2.as_deref(), Some("6"));
+    }
+
+    #[test]
+    fn finds_separate_examples() {
+        let puzzle = PUZZLE.replace("Now count", "```\na\nb\n```\n\nNow count");
+        let found = analyze(&puzzle);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].expected.part_2, None);
+        assert_eq!(found[1].input, "a\nb\n");
+        assert_eq!(found[1].expected.part_1, None);
+        assert_eq!(found[1].expected.part_2.as_deref(), Some("6"));
+    }
+
+    #[test]
+    fn handles_first_part_only() {
+        let puzzle = &PUZZLE[..PUZZLE.find("## --- Part Two").unwrap()];
+        let found = analyze(puzzle);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].expected.part_2, None);
+        assert_eq!(analyze("## --- Day 1 ---\n\nNo example.\n"), vec![]);
+    }
+
+    #[test]
+    fn updates_scaffolded_tests() {
+        let source = "fn test_part_one() {
+        let result = part_one(&read_file(\"examples\", YEAR, DAY));
+        assert_eq!(result, None);
+    }
+
+    fn test_part_two() {
+        let result = part_two(&read_file(\"examples\", YEAR, DAY));
+        assert_eq!(result, None);
+    }";
+
+        let updated = update_test(source, "test_part_two", "6", Some(2)).unwrap();
+        assert_eq!(updated.matches("assert_eq!(result, None);").count(), 1);
+        assert_eq!(updated.contains("assert_eq!(result, Some(6));"), true);
+        assert_eq!(
+            updated.contains("read_file_part(\"examples\", YEAR, DAY, 2)"),
+            true
+        );
+
+        let updated = update_test(&updated, "test_part_one", "3", None).unwrap();
+        assert_eq!(updated.contains("assert_eq!(result, Some(3));"), true);
+        assert_eq!(updated.matches("read_file(").count(), 1);
+
+        assert_eq!(update_test(&updated, "test_part_one", "4", None), None);
+        assert_eq!(update_test(source, "test_part_one", "abc", None), None);
+    }
+}
@@ -1,11 +1,14 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
+/// A year can have its own table with a year marker, e.g. `<!--- benchmarking table 2024 --->`.
+/// The readme and the name of the marker can be changed in `aoc.toml`.
+/// Timings measured with the `count-alloc` feature add a column with the heap usage of each part.
 use std::{fs, io};
 
-use crate::template::Day;
+use crate::template::benchmark_report::{Column, markdown_table};
+use crate::template::config::{self, Config};
 use crate::template::timings::Timings;
-
-static MARKER: &str = "<!--- benchmarking table --->";
+use crate::template::{BIN_DIR, Day, Year, bin_name};
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -26,12 +29,12 @@ pub struct TablePosition {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Year, day: Day) -> String {
+    format!("./{BIN_DIR}/{}.rs", bin_name(year, day))
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,78 +55,119 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(
+    prefix: &str,
+    marker: &str,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+) -> String {
+    let header = format!("{prefix} Benchmarks ({year})");
+    let Timings {
+        data,
+        environment,
+        mixed_environments,
+    } = timings;
+
+    // memory columns are only shown for timings measured with the `count-alloc` feature.
+    let has_memory = data
+        .iter()
+        .any(|t| t.part_1_memory.is_some() || t.part_2_memory.is_some());
+    let mut columns = Column::DEFAULT.to_vec();
+    if has_memory {
+        columns.extend([Column::Memory1, Column::Memory2]);
+    }
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut lines: Vec<String> = vec![marker.into(), header, String::new()];
+    lines.extend(markdown_table(year, &data, &columns));
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+    lines.push(String::new());
+    lines.push(format!("**Total: {total_millis:.2}ms**"));
+
+    if let Some(environment) = &environment {
+        lines.push(String::new());
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            "<sub>Measured on {}.</sub>",
+            environment.describe()
         ));
     }
+    if mixed_environments {
+        lines.push(String::new());
+        lines.push("> [!WARNING]".into());
+        lines.push("> Some of these timings were measured on a different machine.".into());
+    }
 
-    lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    config: &Config,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+) -> Result<(), Error> {
+    // prefer the table of the year, fall back to the shared table.
+    let year_marker = config.readme_marker(Some(year));
+    let marker = if s.contains(&year_marker) {
+        year_marker
+    } else {
+        config.readme_marker(None)
+    };
+
+    let positions = locate_table(s, &marker)?;
+    let table = construct_table("##", &marker, year, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
+pub fn update(year: Year, timings: Timings) -> Result<(), Error> {
+    let config = config::get();
+    let path = &config.readme;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, config, year, timings, total_millis)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{MARKER, update_content};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::update_content;
+    use crate::template::alloc::Memory;
+    use crate::template::config::Config;
+    use crate::template::environment::Environment;
+    use crate::template::fixtures::timing;
+    use crate::{day, template::timings::Timing, template::timings::Timings, year};
+
+    const MARKER: &str = "<!--- benchmarking table --->";
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..timing(day!(1))
                 },
                 Timing {
-                    day: day!(2),
+                    parse: Some("5ms".into()),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..timing(day!(2))
                 },
                 Timing {
-                    day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    ..timing(day!(4))
                 },
             ],
+            ..Timings::default()
         }
     }
 
@@ -131,47 +175,94 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &Config::default(),
+            year!(2025),
+            get_mock_timings(),
+            190.0,
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &Config::default(),
+            year!(2025),
+            get_mock_timings(),
+            190.0,
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        update_content(
+            &mut s,
+            &Config::default(),
+            year!(2025),
+            get_mock_timings(),
+            190.0,
+        )
+        .unwrap();
+        assert_eq!(s.contains("## Benchmarks (2025)"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &Config::default(),
+            year!(2025),
+            get_mock_timings(),
+            190.0,
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            &Config::default(),
+            year!(2025),
+            get_mock_timings(),
+            190.0,
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
-        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        assert_eq!(
+            s.matches("## Benchmarks (2025)")
+                .collect::<Vec<&str>>()
+                .len(),
+            1
+        );
     }
 
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &Config::default(),
+            year!(2025),
+            get_mock_timings(),
+            190.0,
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "## Benchmarks (2025)",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2025-01.rs) | `-` | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2025-02.rs) | `5ms` | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2025-04.rs) | `-` | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +271,81 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn prefers_year_table() {
+        let mut s = format!(
+            "{}\n{}\n<!--- benchmarking table 2024 --->\n<!--- benchmarking table 2024 --->",
+            MARKER, MARKER
+        );
+        update_content(
+            &mut s,
+            &Config::default(),
+            year!(2024),
+            get_mock_timings(),
+            190.0,
+        )
+        .unwrap();
+        assert_eq!(s.starts_with(&format!("{}\n{}\n", MARKER, MARKER)), true);
+        assert_eq!(s.contains("## Benchmarks (2024)"), true);
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/2024-01.rs) | `-` | `10ms` | `20ms` |"),
+            true
+        );
+    }
+
+    #[test]
+    fn renders_environment() {
+        let timings = Timings {
+            environment: Some(Environment {
+                cpu: "Apple M2".into(),
+                cores: 8,
+                governor: None,
+                rustc: "rustc 1.95.0".into(),
+                profile: "release".into(),
+                target_features: vec![],
+                dhat: false,
+            }),
+            mixed_environments: true,
+            ..get_mock_timings()
+        };
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, &Config::default(), year!(2025), timings, 190.0).unwrap();
+        let expected = [
+            "**Total: 190.00ms**",
+            "",
+            "<sub>Measured on Apple M2 (8 cores) · rustc 1.95.0 · release profile · dhat off.</sub>",
+            "",
+            "> [!WARNING]",
+            "> Some of these timings were measured on a different machine.",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s.ends_with(&expected), true);
+    }
+
+    #[test]
+    fn renders_memory_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_memory = Some(Memory {
+            peak_bytes: 2048,
+            allocations: 12,
+        });
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, &Config::default(), year!(2025), timings, 190.0).unwrap();
+        assert_eq!(
+            s.contains("| Day | Parse | Part 1 | Part 2 | Memory 1 | Memory 2 |"),
+            true
+        );
+        assert_eq!(
+            s.contains(
+                "| [Day 1](./src/bin/2025-01.rs) | `-` | `10ms` | `20ms` | `2.0 KiB` (12 allocs) | `-` |"
+            ),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 2](./src/bin/2025-02.rs) | `5ms` | `30ms` | `40ms` | `-` | `-` |"),
+            true
+        );
+    }
 }
@@ -0,0 +1,406 @@
+/// Machine-readable records of solution runs.
+/// When the environment variable `AOC_REPORT_FILE` is set, `run_part` appends one JSON line per part to that file.
+/// Solution binaries declare their parts in a first line, see [`declare_parts`].
+/// `run_multi` reads these records back instead of parsing the human-readable output of the solution binaries.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::Path,
+    str::FromStr,
+    sync::OnceLock,
+    time::Duration,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{Day, alloc::Memory};
+
+pub const REPORT_FILE_ENV: &str = "AOC_REPORT_FILE";
+
+/// The part number under which the shared `parse` step of a solution is reported, see `solution!`.
+pub const PARSE_PART: u8 = 0;
+
+/// Outcome of running a single part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    Solved,
+    Unsolved,
+    Panicked,
+    TimedOut,
+    /// The part returned an error, e.g. because the input could not be parsed.
+    Errored,
+}
+
+impl PartStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            PartStatus::Solved => "solved",
+            PartStatus::Unsolved => "unsolved",
+            PartStatus::Panicked => "panicked",
+            PartStatus::TimedOut => "timed_out",
+            PartStatus::Errored => "errored",
+        }
+    }
+
+    /// Whether the part failed to run, as opposed to returning a result or `None`.
+    pub fn is_failure(self) -> bool {
+        matches!(
+            self,
+            PartStatus::Panicked | PartStatus::TimedOut | PartStatus::Errored
+        )
+    }
+}
+
+impl FromStr for PartStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(PartStatus::Solved),
+            "unsolved" => Ok(PartStatus::Unsolved),
+            "panicked" => Ok(PartStatus::Panicked),
+            "timed_out" => Ok(PartStatus::TimedOut),
+            "errored" => Ok(PartStatus::Errored),
+            s => Err(format!("unknown part status `{s}`.")),
+        }
+    }
+}
+
+/// Represents the result of running a single part of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartReport {
+    pub day: Day,
+    /// 1 or 2, or [`PARSE_PART`] for the shared parse step.
+    pub part: u8,
+    pub status: PartStatus,
+    pub answer: Option<String>,
+    /// Describes why a part failed to run, see [`PartStatus::is_failure`].
+    pub error: Option<String>,
+    pub samples: Vec<Duration>,
+    /// Heap usage of the first run, only counted with the `count-alloc` feature.
+    pub memory: Option<Memory>,
+}
+
+impl PartReport {
+    /// Mean duration over all samples, `None` if the part was not timed.
+    pub fn mean(&self) -> Option<Duration> {
+        let count = u32::try_from(self.samples.len()).ok()?;
+        if count == 0 {
+            return None;
+        }
+        Some(self.samples.iter().sum::<Duration>() / count)
+    }
+}
+
+/// Append a report to the file named by `AOC_REPORT_FILE`. Does nothing if the variable is not set.
+pub fn emit(report: &PartReport) {
+    append(&JsonValue::from(report));
+}
+
+static DECLARED_PARTS: OnceLock<Vec<u8>> = OnceLock::new();
+
+/// Declare the parts a solution runs, including [`PARSE_PART`], before running any of them.
+/// A killed solution can then be told apart from one that does not have a part, see `run_multi`.
+pub fn declare_parts(day: Day, parts: &[u8]) {
+    let _ = DECLARED_PARTS.set(parts.to_vec());
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert("day".into(), JsonValue::String(day.to_string()));
+    map.insert(
+        "parts".into(),
+        JsonValue::Array(
+            parts
+                .iter()
+                .map(|part| JsonValue::Number(f64::from(*part)))
+                .collect(),
+        ),
+    );
+    append(&JsonValue::Object(map));
+}
+
+/// The parts passed to [`declare_parts`], empty if the solution did not declare them.
+pub fn declared_parts() -> &'static [u8] {
+    DECLARED_PARTS.get().map_or(&[], Vec::as_slice)
+}
+
+fn append(json: &JsonValue) {
+    let Ok(path) = std::env::var(REPORT_FILE_ENV) else {
+        return;
+    };
+
+    let line = match json.stringify() {
+        Ok(line) => line,
+        Err(e) => {
+            eprintln!("Failed to serialize part report: {e}");
+            return;
+        }
+    };
+
+    let written = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| writeln!(file, "{line}"));
+
+    if let Err(e) = written {
+        eprintln!("Failed to write part report to \"{path}\": {e}");
+    }
+}
+
+/// The records of a report file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Records {
+    /// The parts the solution declared, `None` if it exited before declaring them, see [`declare_parts`].
+    pub parts: Option<Vec<u8>>,
+    pub reports: Vec<PartReport>,
+}
+
+/// Read all records from a JSON lines file. A missing file yields no records.
+pub fn read_reports(path: &Path) -> Result<Records, String> {
+    match fs::read_to_string(path) {
+        Ok(contents) => parse_reports(&contents),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Records::default()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Parse records from JSON lines, skipping blank lines.
+pub fn parse_reports(contents: &str) -> Result<Records, String> {
+    let mut records = Records::default();
+
+    for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+        let json = JsonValue::from_str(line).or(Err("not valid JSON line."))?;
+
+        let parts = json
+            .get::<HashMap<String, JsonValue>>()
+            .and_then(|map| map.get("parts"));
+
+        match parts {
+            Some(parts) => {
+                let parts = parts
+                    .get::<Vec<JsonValue>>()
+                    .and_then(|parts| parts.iter().map(part_number).collect())
+                    .ok_or("Expected parts to be an array of 0, 1 or 2.")?;
+                records.parts = Some(parts);
+            }
+            None => records.reports.push(PartReport::try_from(&json)?),
+        }
+    }
+
+    Ok(records)
+}
+
+/// A part number, see [`PartReport::part`].
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn part_number(value: &JsonValue) -> Option<u8> {
+    value
+        .get::<f64>()
+        .filter(|p| [f64::from(PARSE_PART), 1.0, 2.0].contains(p))
+        .map(|p| *p as u8)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartReport> for JsonValue {
+    fn from(value: &PartReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        if let Some(error) = &value.error {
+            map.insert("error".into(), JsonValue::String(error.clone()));
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let samples = value
+            .samples
+            .iter()
+            .map(|d| JsonValue::Number(d.as_nanos() as f64))
+            .collect();
+        map.insert("sample_nanos".into(), JsonValue::Array(samples));
+
+        if let Some(memory) = &value.memory {
+            map.insert("memory".into(), JsonValue::from(memory));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartReport {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected report.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(part_number)
+            .ok_or("Expected report.part to be 0, 1 or 2.")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected report.status to be a string.")?
+            .parse()?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        let error = json
+            .get("error")
+            .map(|v| {
+                v.get::<String>()
+                    .ok_or("Expected report.error to be a string.")
+            })
+            .transpose()?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = json
+            .get("sample_nanos")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected report.sample_nanos to be an array.")?
+            .iter()
+            .map(|v| {
+                v.get::<f64>()
+                    .map(|nanos| Duration::from_nanos(*nanos as u64))
+                    .ok_or("Expected report.sample_nanos to contain numbers.")
+            })
+            .collect::<Result<_, _>>()?;
+
+        let memory = json.get("memory").map(Memory::try_from).transpose()?;
+
+        Ok(PartReport {
+            day,
+            part,
+            status,
+            answer: answer.cloned(),
+            error: error.cloned(),
+            samples,
+            memory,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{PARSE_PART, PartReport, PartStatus, parse_reports};
+    use crate::{day, template::alloc::Memory};
+
+    fn get_mock_report() -> PartReport {
+        PartReport {
+            day: day!(3),
+            part: 2,
+            status: PartStatus::Solved,
+            answer: Some("Part 1: 1 (2s @ 5 samples)\nline two".into()),
+            error: None,
+            samples: vec![Duration::from_nanos(100), Duration::from_nanos(300)],
+            memory: Some(Memory {
+                peak_bytes: 4096,
+                allocations: 3,
+            }),
+        }
+    }
+
+    #[test]
+    fn roundtrips_reports() {
+        let report = get_mock_report();
+        let line = JsonValue::from(&report).stringify().unwrap();
+        assert_eq!(line.contains('\n'), false);
+        let parsed = parse_reports(&format!("{line}\n\n{line}\n")).unwrap();
+        assert_eq!(parsed.reports, vec![report.clone(), report]);
+        assert_eq!(parsed.parts, None);
+    }
+
+    #[test]
+    fn computes_mean_duration() {
+        let mut report = get_mock_report();
+        assert_eq!(report.mean(), Some(Duration::from_nanos(200)));
+        report.samples.clear();
+        assert_eq!(report.mean(), None);
+    }
+
+    #[test]
+    fn handles_unsolved_parts() {
+        let line = r#"{ "day": "01", "part": 1, "status": "unsolved", "answer": null, "sample_nanos": [] }"#;
+        let parsed = parse_reports(line).unwrap().reports;
+        assert_eq!(parsed[0].status, PartStatus::Unsolved);
+        assert_eq!(parsed[0].answer, None);
+        assert_eq!(parsed[0].memory, None);
+    }
+
+    #[test]
+    fn handles_failed_parts() {
+        let line = r#"{ "day": "10", "part": 2, "status": "panicked", "answer": null, "error": "panicked: explicit panic", "sample_nanos": [] }"#;
+        let parsed = parse_reports(line).unwrap().reports;
+        assert_eq!(parsed[0].status, PartStatus::Panicked);
+        assert_eq!(parsed[0].status.is_failure(), true);
+        assert_eq!(parsed[0].error.as_deref(), Some("panicked: explicit panic"));
+        assert_eq!(PartStatus::TimedOut.is_failure(), true);
+        assert_eq!(PartStatus::Unsolved.is_failure(), false);
+
+        let report = PartReport {
+            status: PartStatus::TimedOut,
+            error: Some("timed out after 1s".into()),
+            ..get_mock_report()
+        };
+        let line = JsonValue::from(&report).stringify().unwrap();
+        assert_eq!(parse_reports(&line).unwrap().reports, vec![report]);
+    }
+
+    #[test]
+    fn handles_parse_step() {
+        let line = r#"{ "day": "01", "part": 0, "status": "solved", "answer": null, "sample_nanos": [5] }"#;
+        let parsed = parse_reports(line).unwrap().reports;
+        assert_eq!(parsed[0].part, PARSE_PART);
+    }
+
+    #[test]
+    fn reads_declared_parts() {
+        let contents = r#"{ "day": "01", "parts": [0, 1] }
+{ "day": "01", "part": 0, "status": "solved", "answer": null, "sample_nanos": [5] }"#;
+        let records = parse_reports(contents).unwrap();
+        assert_eq!(records.parts, Some(vec![PARSE_PART, 1]));
+        assert_eq!(records.reports.len(), 1);
+        assert_eq!(
+            parse_reports(r#"{ "day": "01", "parts": [3] }"#).is_err(),
+            true
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_part() {
+        let line =
+            r#"{ "day": "01", "part": 3, "status": "solved", "answer": "1", "sample_nanos": [] }"#;
+        parse_reports(line).unwrap();
+    }
+}
@@ -1,257 +1,722 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Display,
+    io::{self, Write},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
-use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, Year};
 
 use super::{
-    all_days,
-    timings::{Timing, Timings},
+    report::{PARSE_PART, PartReport, PartStatus},
+    runner::{RunOptions, Solution},
+    timeouts::DayTimeouts,
+    timings::Timings,
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
-
-    let mut need_space = false;
+/// Determines how `run_multi` executes the solution of each day.
+#[derive(Clone, Copy)]
+pub enum Executor<'a> {
+    /// Run the solutions compiled into the current binary.
+    InProcess(&'a [Solution]),
+    /// Build `--bin <year>-<day>` and spawn it as a child process per day.
+    /// Children that exceed the timeout of their day are killed.
+    /// With more than one job, days run concurrently and their output is buffered to print in day order.
+    /// Their bins are still built one after another before any day runs, see [`run_parallel`].
+    Isolated {
+        is_release: bool,
+        timeouts: &'a DayTimeouts,
+        jobs: usize,
+    },
+}
 
+/// Run the solutions of the given days of a year in order, returning the reports of all parts that were run.
+/// Prints the total run time if the parts are benched and a summary of the status of every day.
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    executor: Executor,
+    options: RunOptions,
+) -> Vec<PartReport> {
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let days: Vec<Day> = year
+        .days()
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
-
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        .collect();
+
+    let reports = match executor {
+        Executor::Isolated {
+            is_release,
+            timeouts,
+            jobs,
+        } if jobs > 1 => run_parallel(year, &days, options, is_release, timeouts, jobs),
+        _ => {
+            let mut reports: Vec<PartReport> = vec![];
+
+            for (i, day) in days.iter().copied().enumerate() {
+                print_day_header(day, i > 0);
+
+                let day_reports = match executor {
+                    Executor::InProcess(solutions) => {
+                        in_process::run_solution(solutions, year, day, options)
+                    }
+                    Executor::Isolated {
+                        is_release,
+                        timeouts,
+                        ..
+                    } => child_commands::run_solution(
+                        year,
+                        day,
+                        options,
+                        is_release,
+                        timeouts.get(day),
+                    )
+                    .unwrap_or_else(|e| {
+                        eprintln!("Could not run the solution: {e}");
+                        vec![errored_report(day, &e.to_string())]
+                    }),
+                };
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+                if day_reports.is_empty() {
+                    println!("Not solved.");
+                }
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+                reports.extend(day_reports);
             }
-        });
 
-    if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
+            reports
+        }
+    };
+
+    if options.bench.is_some() {
+        let total_millis = Timings::from_reports(&reports).total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
     }
+
+    print_summary(year, days_to_run, &reports);
+
+    reports
+}
+
+fn print_day_header(day: Day, need_space: bool) {
+    if need_space {
+        println!();
+    }
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+/// Run the bins of the given days on `jobs` threads, printing their buffered output in day order.
+/// Only the runs are concurrent: bins are built up-front and one after another,
+/// as cargo locks the build directory and concurrent builds would wait on each other anyway.
+fn run_parallel(
+    year: Year,
+    days: &[Day],
+    options: RunOptions,
+    is_release: bool,
+    timeouts: &DayTimeouts,
+    jobs: usize,
+) -> Vec<PartReport> {
+    let executables: Vec<_> = days
+        .iter()
+        .map(|day| child_commands::build_solution(year, *day, is_release))
+        .collect();
+
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let sender = sender.clone();
+            let (next, executables) = (&next, &executables);
+
+            scope.spawn(move || {
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(day) = days.get(i).copied() else {
+                        break;
+                    };
+
+                    let mut output = vec![];
+                    let day_reports = match &executables[i] {
+                        Ok(Some(executable)) => child_commands::run_executable(
+                            year,
+                            day,
+                            executable,
+                            options,
+                            timeouts.get(day),
+                            Some(&mut output),
+                        )
+                        .map_err(|e| e.to_string()),
+                        Ok(None) => Ok(vec![]),
+                        Err(e) => Err(e.to_string()),
+                    }
+                    .unwrap_or_else(|e| {
+                        let _ = writeln!(output, "Could not run the solution: {e}");
+                        vec![errored_report(day, &e)]
+                    });
+
+                    if sender.send((i, day_reports, output)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        // print days as soon as all days before them have finished.
+        let mut finished = BTreeMap::new();
+        let mut printed = 0;
+        let mut reports: Vec<PartReport> = vec![];
+
+        for (i, day_reports, output) in receiver {
+            finished.insert(i, (day_reports, output));
+
+            while let Some((day_reports, output)) = finished.remove(&printed) {
+                print_day_header(days[printed], printed > 0);
+                let _ = io::stdout().write_all(&output);
+
+                if day_reports.is_empty() {
+                    println!("Not solved.");
+                }
+
+                reports.extend(day_reports);
+                printed += 1;
+            }
+        }
+
+        reports
+    })
+}
+
+/// The report of a day whose solution could not be built or run, filed under [`PARSE_PART`] as none of its parts ran.
+fn errored_report(day: Day, error: &str) -> PartReport {
+    PartReport {
+        day,
+        part: PARSE_PART,
+        status: PartStatus::Errored,
+        answer: None,
+        error: Some(format!("could not run the solution: {error}")),
+        samples: vec![],
+        memory: None,
+    }
+}
+
+/// Whether any part failed to run, commands exit with a non-zero code in that case.
+pub fn has_failures(reports: &[PartReport]) -> bool {
+    reports.iter().any(|r| r.status.is_failure())
+}
+
+/// Overall status of a day, the worst status of its parts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum DayStatus {
+    Ok,
+    Unsolved,
+    Errored,
+    TimedOut,
+    Panicked,
+}
+
+impl DayStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            DayStatus::Ok => "ok",
+            DayStatus::Unsolved => "unsolved",
+            DayStatus::Errored => "errored",
+            DayStatus::TimedOut => "timed out",
+            DayStatus::Panicked => "panicked",
+        }
+    }
+}
+
+fn day_status(day: Day, reports: &[PartReport]) -> DayStatus {
+    reports
+        .iter()
+        .filter(|r| r.day == day)
+        .map(|r| match r.status {
+            PartStatus::Solved => DayStatus::Ok,
+            PartStatus::Unsolved => DayStatus::Unsolved,
+            PartStatus::Errored => DayStatus::Errored,
+            PartStatus::TimedOut => DayStatus::TimedOut,
+            PartStatus::Panicked => DayStatus::Panicked,
+        })
+        .max()
+        .unwrap_or(DayStatus::Unsolved)
+}
+
+fn print_summary(year: Year, days_to_run: &HashSet<Day>, reports: &[PartReport]) {
+    println!();
+    println!("{ANSI_BOLD}Summary{ANSI_RESET}");
+    println!();
+    println!("| Day | Status |");
+    println!("| :---: | :---: |");
+
+    let mut counts = [0; 5];
+
+    for day in year.days().filter(|day| days_to_run.contains(day)) {
+        let status = day_status(day, reports);
+        counts[status as usize] += 1;
+
+        // NOTE: only the first line of an error fits into the table, e.g. without the snippet of a parse error.
+        let failures: Vec<&str> = reports
+            .iter()
+            .filter(|r| r.day == day)
+            .filter_map(|r| r.error.as_deref()?.lines().next())
+            .collect();
+
+        if failures.is_empty() {
+            println!("| {day} | {} |", status.as_str());
+        } else {
+            println!("| {day} | {} ({}) |", status.as_str(), failures.join(", "));
+        }
+    }
+
+    println!();
+    println!(
+        "{} ok, {} unsolved, {} errored, {} panicked, {} timed out",
+        counts[DayStatus::Ok as usize],
+        counts[DayStatus::Unsolved as usize],
+        counts[DayStatus::Errored as usize],
+        counts[DayStatus::Panicked as usize],
+        counts[DayStatus::TimedOut as usize],
+    );
 }
 
-#[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
+    /// `cargo build` failed, its diagnostics have been forwarded already.
+    Build,
     IO(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "broken pipe"),
+            Error::Build => write!(f, "the solution did not compile"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Solutions compiled into the main binary are run directly, without spawning `cargo`.
+pub mod in_process {
+    use std::fs;
+
+    use crate::template::{
+        Day, Year, data_dir,
+        report::PartReport,
+        runner::{RunOptions, Solution},
+    };
+
+    /// Run the registered solution for a given day and collect its part reports.
+    pub fn run_solution(
+        solutions: &[Solution],
+        year: Year,
+        day: Day,
+        options: RunOptions,
+    ) -> Vec<PartReport> {
+        // skip days that have not been scaffolded yet.
+        let Some(solution) = solutions.iter().find(|s| s.year == year && s.day == day) else {
+            return vec![];
+        };
+
+        let input_path = data_dir(year).join("inputs").join(format!("{day}.txt"));
+        let input = match fs::read_to_string(&input_path) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!(
+                    "Could not read input file \"{}\": {e}",
+                    input_path.display()
+                );
+                return vec![];
+            }
+        };
+
+        (solution.run)(&input, &options)
+    }
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// Every solution also lives in an isolated binary.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting their reports.
 pub mod child_commands {
-    use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use super::Error;
+    use crate::template::{
+        ANSI_BOLD, ANSI_RESET, Day, Year, bin_name, bin_path,
+        report::{self, PartReport, PartStatus, REPORT_FILE_ENV, Records},
+        runner::RunOptions,
+    };
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        collections::HashMap,
+        env, fs,
+        io::{self, BufRead, BufReader, Read, Write},
+        path::{Path, PathBuf},
+        process::{Child, Command, Stdio},
+        str::FromStr,
         thread,
+        time::{Duration, Instant},
     };
+    use tinyjson::JsonValue;
+
+    /// Interval at which a running child is checked for having exited.
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+    /// Run the solution bin for a given day and collect the part reports it emitted.
+    /// If the bin runs for longer than `timeout`, it is killed and its remaining parts are reported as timed out.
+    pub fn run_solution(
+        year: Year,
+        day: Day,
+        options: RunOptions,
+        is_release: bool,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<PartReport>, Error> {
+        // the bin is built up-front so that compiling does not count towards the timeout,
+        // and run directly so that killing it does not leave an orphan behind `cargo run`.
+        match build_solution(year, day, is_release)? {
+            Some(executable) => run_executable(year, day, &executable, options, timeout, None),
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Run a built solution bin, see [`run_solution`].
+    /// If `output` is passed, everything the bin prints is appended to it instead of being forwarded.
+    pub fn run_executable(
+        year: Year,
+        day: Day,
+        executable: &Path,
+        options: RunOptions,
+        timeout: Option<Duration>,
+        mut output: Option<&mut Vec<u8>>,
+    ) -> Result<Vec<PartReport>, Error> {
+        let report_path = get_report_path(year, day);
+        remove_report(&report_path)?;
+
+        // mirror `--time` flag, bench config and part timeout to child invocations.
+        let mut cmd = Command::new(executable);
+        cmd.args(options.to_args())
+            .env(REPORT_FILE_ENV, &report_path);
+
+        // output is forwarded or buffered as-is, results are read from the report file afterwards.
+        let reader = match output {
+            Some(_) => {
+                let (reader, writer) = io::pipe()?;
+                cmd.stdout(writer.try_clone()?).stderr(writer);
+                Some(reader)
+            }
+            None => {
+                cmd.stdout(Stdio::inherit()).stderr(Stdio::inherit());
+                None
+            }
+        };
+
+        let mut child = cmd.spawn()?;
+        // NOTE: the pipe only reaches EOF once the writers held by `cmd` are dropped as well.
+        drop(cmd);
+
+        let timed_out = thread::scope(|scope| {
+            let copy = reader
+                .zip(output.as_deref_mut())
+                .map(|(mut reader, output)| scope.spawn(move || reader.read_to_end(output)));
+
+            let timed_out = match timeout {
+                Some(timeout) => !wait_with_timeout(&mut child, timeout)?,
+                None => {
+                    child.wait()?;
+                    false
+                }
+            };
+
+            if let Some(copy) = copy {
+                copy.join().map_err(|_| Error::BrokenPipe)??;
+            }
+
+            Ok::<_, Error>(timed_out)
+        })?;
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+        let records = report::read_reports(&report_path).unwrap_or_else(|e| {
+            eprintln!("Could not read part reports: {e}");
+            Records::default()
+        });
+        let mut reports = records.reports;
+        remove_report(&report_path)?;
+
+        if let Some(timeout) = timeout.filter(|_| timed_out) {
+            let message = format!(
+                "\n{ANSI_BOLD}Timed out{ANSI_RESET} after {timeout:?}, the solution was killed."
+            );
+            match output {
+                Some(output) => writeln!(output, "{message}")?,
+                None => println!("{message}"),
+            }
+            let parts = records.parts.unwrap_or_default();
+            reports.extend(timed_out_reports(day, timeout, &parts, &reports));
+        }
+
+        Ok(reports)
+    }
+
+    /// Build the solution bin for a given day, returning the path of its executable.
+    /// Compiler diagnostics are forwarded, `None` is returned if the day has not been scaffolded.
+    pub fn build_solution(
+        year: Year,
+        day: Day,
+        is_release: bool,
+    ) -> Result<Option<PathBuf>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !bin_path(year, day).exists() {
+            return Ok(None);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let bin = bin_name(year, day);
+
+        let mut args: Vec<String> = vec![
+            "build".into(),
+            "--quiet".into(),
+            "--bin".into(),
+            bin.clone(),
+            "--message-format=json-render-diagnostics".into(),
+        ];
 
         if is_release {
-            args.push("--release");
+            args.push("--release".into());
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        // solutions count their allocations or profile their heap if the main binary does, see `alloc`.
+        // `Environment::capture` records these features for the timings of the solutions.
+        for (feature, enabled) in [
+            ("count-alloc", cfg!(feature = "count-alloc")),
+            ("dhat-heap", cfg!(feature = "dhat-heap")),
+        ] {
+            if enabled {
+                args.push("--features".into());
+                args.push(feature.into());
+            }
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
-
         let mut cmd = Command::new("cargo")
             .args(&args)
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
+            .stderr(Stdio::inherit())
             .spawn()?;
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
-
-        let mut output = vec![];
-
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+        let stdout = BufReader::new(cmd.stdout.take().ok_or(Error::BrokenPipe)?);
 
+        let mut executable = None;
         for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            if let Some(path) = parse_executable(&line?, &bin) {
+                executable = Some(path);
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        if !cmd.wait()?.success() {
+            return Err(Error::Build);
+        }
 
-        Ok(output)
+        Ok(executable)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+    /// Extract the executable of `bin` from a line of cargo's JSON build messages.
+    pub(super) fn parse_executable(line: &str, bin: &str) -> Option<PathBuf> {
+        let json = JsonValue::from_str(line).ok()?;
+        let message = json.get::<HashMap<String, JsonValue>>()?;
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        let is_bin = message
+            .get("target")
+            .and_then(|target| target.get::<HashMap<String, JsonValue>>())
+            .and_then(|target| target.get("name"))
+            .and_then(|name| name.get::<String>())
+            .is_some_and(|name| name == bin);
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+        if !is_bin {
+            return None;
+        }
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+        message
+            .get("executable")
+            .and_then(|path| path.get::<String>())
+            .map(PathBuf::from)
+    }
 
-                timings.total_nanos += nanos;
-            });
+    /// Wait for the child to exit, killing it once `timeout` has passed.
+    /// Returns whether the child exited on its own.
+    fn wait_with_timeout(child: &mut Child, timeout: Duration) -> Result<bool, Error> {
+        let deadline = Instant::now() + timeout;
 
-        timings
-    }
+        while Instant::now() < deadline {
+            if child.try_wait()?.is_some() {
+                return Ok(true);
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+        // the child may have exited in the meantime, killing it is a no-op then.
+        if child.try_wait()?.is_some() {
+            return Ok(true);
+        }
+        child.kill()?;
+        child.wait()?;
+        Ok(false)
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .next_back()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    /// Reports for the declared parts of a killed solution that did not report back before the timeout.
+    pub(super) fn timed_out_reports(
+        day: Day,
+        timeout: Duration,
+        parts: &[u8],
+        reports: &[PartReport],
+    ) -> Vec<PartReport> {
+        parts
+            .iter()
+            .copied()
+            .filter(|part| !reports.iter().any(|r| r.part == *part))
+            .map(|part| PartReport {
+                day,
+                part,
+                status: PartStatus::TimedOut,
+                answer: None,
+                error: Some(format!("day timed out after {timeout:?}")),
+                samples: vec![],
+                memory: None,
+            })
+            .collect()
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    fn get_report_path(year: Year, day: Day) -> PathBuf {
+        env::temp_dir().join(format!(
+            "aoc-report-{}-{}.jsonl",
+            std::process::id(),
+            bin_name(year, day)
+        ))
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+    fn remove_report(path: &Path) -> Result<(), Error> {
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
         }
+    }
+}
 
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{path::PathBuf, time::Duration};
 
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+    use super::{
+        DayStatus, Error,
+        child_commands::{parse_executable, timed_out_reports},
+        day_status, errored_report, has_failures,
+    };
+    use crate::{
+        day,
+        template::report::{PARSE_PART, PartReport, PartStatus},
+    };
+
+    fn report(part: u8, status: PartStatus) -> PartReport {
+        PartReport {
+            day: day!(1),
+            part,
+            status,
+            answer: None,
+            error: None,
+            samples: vec![],
+            memory: None,
         }
     }
+
+    #[test]
+    fn uses_worst_part_status() {
+        let solved = report(1, PartStatus::Solved);
+        let unsolved = report(2, PartStatus::Unsolved);
+        let panicked = report(2, PartStatus::Panicked);
+        let timed_out = report(2, PartStatus::TimedOut);
+        assert_eq!(day_status(day!(1), &[solved.clone()]), DayStatus::Ok);
+        assert_eq!(
+            day_status(day!(1), &[solved.clone(), unsolved]),
+            DayStatus::Unsolved
+        );
+        assert_eq!(
+            day_status(day!(1), &[solved.clone(), timed_out]),
+            DayStatus::TimedOut
+        );
+        assert_eq!(
+            day_status(day!(1), &[solved.clone(), panicked]),
+            DayStatus::Panicked
+        );
+        assert_eq!(day_status(day!(2), &[solved]), DayStatus::Unsolved);
+    }
+
+    #[test]
+    fn reports_days_that_could_not_run() {
+        let report = errored_report(day!(1), &Error::Build.to_string());
+        assert_eq!(day_status(day!(1), &[report.clone()]), DayStatus::Errored);
+        assert_eq!(
+            report.error.as_deref(),
+            Some("could not run the solution: the solution did not compile")
+        );
+
+        let report = errored_report(day!(1), "No such file or directory");
+        assert_eq!(day_status(day!(1), &[report.clone()]), DayStatus::Errored);
+        assert_eq!(has_failures(&[report.clone()]), true);
+        assert_eq!(
+            report.error.as_deref(),
+            Some("could not run the solution: No such file or directory")
+        );
+    }
+
+    #[test]
+    fn reports_missing_parts_as_timed_out() {
+        let timeout = Duration::from_secs(5);
+        let solved = report(1, PartStatus::Solved);
+        let reports = timed_out_reports(day!(1), timeout, &[1, 2], &[solved]);
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].part, 2);
+        assert_eq!(reports[0].status, PartStatus::TimedOut);
+        assert_eq!(reports[0].error.as_deref(), Some("day timed out after 5s"));
+        assert_eq!(timed_out_reports(day!(1), timeout, &[1, 2], &[]).len(), 2);
+
+        let parsing = timed_out_reports(day!(1), timeout, &[PARSE_PART, 1, 2], &[]);
+        assert_eq!(
+            parsing.iter().map(|r| r.part).collect::<Vec<_>>(),
+            vec![PARSE_PART, 1, 2]
+        );
+    }
+
+    #[test]
+    fn reports_only_declared_parts() {
+        let timeout = Duration::from_secs(5);
+        let reports = timed_out_reports(day!(1), timeout, &[1], &[]);
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].part, 1);
+        assert_eq!(
+            timed_out_reports(day!(1), timeout, &[2], &[report(2, PartStatus::Solved)]),
+            vec![]
+        );
+        assert_eq!(timed_out_reports(day!(1), timeout, &[], &[]), vec![]);
+    }
+
+    #[test]
+    fn parses_build_messages() {
+        let artifact = r#"{"reason":"compiler-artifact","target":{"name":"2025-01","kind":["bin"]},"executable":"/target/release/2025-01"}"#;
+        let library = r#"{"reason":"compiler-artifact","target":{"name":"advent_of_code","kind":["lib"]},"executable":null}"#;
+        assert_eq!(
+            parse_executable(artifact, "2025-01"),
+            Some(PathBuf::from("/target/release/2025-01"))
+        );
+        assert_eq!(parse_executable(artifact, "2025-02"), None);
+        assert_eq!(parse_executable(library, "advent_of_code"), None);
+        assert_eq!(parse_executable("not json", "2025-01"), None);
+    }
 }
@@ -1,63 +1,491 @@
 /// Encapsulates code that interacts with solution functions.
+use std::any::Any;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{Write, stdout};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::alloc::{self, Memory};
+use crate::template::answer::{Answer, PartResult};
+use crate::template::aoc_client::{AocClient, SubmissionOutcome};
+use crate::template::report::{self, PARSE_PART, PartReport, PartStatus};
+use crate::template::stats::Stats;
+use crate::template::submissions::{self, SubmitError};
+use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, Year, answers};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Controls how a part is benched.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Time spent running the part before samples are recorded.
+    pub warmup: Duration,
+    /// Approximate time spent recording samples.
+    pub budget: Duration,
+    pub min_samples: u32,
+    pub max_samples: u32,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig {
+            warmup: Duration::from_millis(100),
+            budget: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10_000,
+        }
+    }
+}
+
+impl BenchConfig {
+    /// Arguments that reproduce this config in a solution binary, see [`RunOptions::from_args`].
+    pub fn to_args(&self) -> Vec<String> {
+        vec![
+            "--warmup".into(),
+            self.warmup.as_millis().to_string(),
+            "--budget".into(),
+            self.budget.as_millis().to_string(),
+            "--min-samples".into(),
+            self.min_samples.to_string(),
+            "--max-samples".into(),
+            self.max_samples.to_string(),
+        ]
+    }
+
+    fn from_args(args: &[String]) -> Self {
+        let value = |flag: &str| -> Option<u64> {
+            let index = args.iter().position(|x| x == flag)?;
+            match args.get(index + 1).map(|x| x.parse()) {
+                Some(Ok(value)) => Some(value),
+                _ => {
+                    eprintln!("Unexpected command-line input. Format: {flag} <number>");
+                    process::exit(1);
+                }
+            }
+        };
+        let count = |flag: &str| value(flag).map(|x| u32::try_from(x).unwrap_or(u32::MAX));
+
+        let defaults = BenchConfig::default();
+        BenchConfig {
+            warmup: value("--warmup").map_or(defaults.warmup, Duration::from_millis),
+            budget: value("--budget").map_or(defaults.budget, Duration::from_millis),
+            min_samples: count("--min-samples").unwrap_or(defaults.min_samples),
+            max_samples: count("--max-samples").unwrap_or(defaults.max_samples),
+        }
+    }
+}
+
+/// Options that control how the parts of a solution are run.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions {
+    /// Bench every part with this config instead of running it once.
+    pub bench: Option<BenchConfig>,
+    /// Submit the result of this part once it has been computed.
+    pub submit: Option<u8>,
+    /// Give up on a part that runs longer than this, including benching. The process exits then, see [`run_guarded`].
+    pub part_timeout: Option<Duration>,
+}
+
+impl RunOptions {
+    /// Parse the arguments passed to a solution binary, e.g. by `solve` or `run_multi`.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let submit = args.iter().position(|x| x == "--submit").map(|index| {
+            match args.get(index + 1).map(|x| x.parse::<u8>()) {
+                Some(Ok(part)) => part,
+                _ => {
+                    eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+                    process::exit(1);
+                }
+            }
+        });
+
+        let bench = args
+            .iter()
+            .any(|x| x == "--time")
+            .then(|| BenchConfig::from_args(&args));
+
+        let part_timeout = args
+            .iter()
+            .position(|x| x == "--part-timeout")
+            .map(|index| {
+                match args
+                    .get(index + 1)
+                    .and_then(|x| x.parse().ok())
+                    .and_then(|x| Duration::try_from_secs_f64(x).ok())
+                {
+                    Some(timeout) => timeout,
+                    None => {
+                        eprintln!(
+                            "Unexpected command-line input. Format: --part-timeout <seconds>"
+                        );
+                        process::exit(1);
+                    }
+                }
+            });
+
+        RunOptions {
+            bench,
+            submit,
+            part_timeout,
+        }
+    }
+
+    /// Arguments that reproduce these options in a solution binary, see [`RunOptions::from_args`].
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args: Vec<String> = vec![];
+
+        if let Some(bench) = self.bench {
+            args.push("--time".into());
+            args.extend(bench.to_args());
+        }
+
+        if let Some(part) = self.submit {
+            args.push("--submit".into());
+            args.push(part.to_string());
+        }
+
+        if let Some(timeout) = self.part_timeout {
+            args.push("--part-timeout".into());
+            args.push(timeout.as_secs_f64().to_string());
+        }
+
+        args
+    }
+}
+
+/// A solution compiled into the main binary, see `build.rs`.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub year: Year,
+    pub day: Day,
+    pub run: fn(&str, &RunOptions) -> Vec<PartReport>,
+}
+
+/// Why a part did not produce a result.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Failure {
+    Panicked(String),
+    TimedOut(Duration),
+    /// The part returned an error, see [`PartResult`].
+    Errored(String),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Panicked(message) => write!(f, "panicked: {message}"),
+            Failure::TimedOut(timeout) => write!(f, "timed out after {timeout:?}"),
+            Failure::Errored(message) => write!(f, "error: {message}"),
+        }
+    }
+}
+
+impl Failure {
+    fn status(&self) -> PartStatus {
+        match self {
+            Failure::Panicked(_) => PartStatus::Panicked,
+            Failure::TimedOut(_) => PartStatus::TimedOut,
+            Failure::Errored(_) => PartStatus::Errored,
+        }
+    }
+}
+
+/// Stack size of the threads that run parts, solutions tend to recurse deeply.
+const PART_STACK_SIZE: usize = 256 * 1024 * 1024;
+
+/// Run `func` on its own thread, catching panics.
+/// A running part cannot be stopped, so once `timeout` has passed the failure is passed to `on_timeout` and the process exits.
+/// Part timeouts are therefore only enforced in solution binaries, which `run_multi` runs as child processes with `--isolated`.
+fn run_guarded<R: Send>(
+    func: impl FnOnce() -> R + Send,
+    timeout: Option<Duration>,
+    on_timeout: impl FnOnce(&Failure),
+) -> Result<R, Failure> {
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel();
+
+        let spawned = thread::Builder::new()
+            .stack_size(PART_STACK_SIZE)
+            .spawn_scoped(scope, move || {
+                let _ = sender.send(panic::catch_unwind(AssertUnwindSafe(func)));
+            });
+
+        if let Err(e) = spawned {
+            return Err(Failure::Panicked(format!("could not spawn thread: {e}")));
+        }
+
+        let result = match timeout {
+            Some(timeout) => match receiver.recv_timeout(timeout) {
+                Ok(result) => result,
+                Err(RecvTimeoutError::Timeout) => {
+                    on_timeout(&Failure::TimedOut(timeout));
+                    let _ = stdout().flush();
+                    process::exit(1);
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(Failure::Panicked("thread exited".into()));
+                }
+            },
+            None => receiver
+                .recv()
+                .map_err(|_| Failure::Panicked("thread exited".into()))?,
+        };
+
+        result.map_err(|payload| Failure::Panicked(panic_message(payload.as_ref())))
+    })
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".into()
+    }
+}
+
+/// Run a part of a solution on its own thread, see [`run_guarded`].
+/// Panics, time-outs and returned errors are reported with a distinct status instead of aborting the run.
+pub fn run_part<I, R, F>(
+    func: F,
+    input: I,
+    year: Year,
+    day: Day,
+    part: u8,
+    options: &RunOptions,
+) -> PartReport
+where
+    I: Copy + Send,
+    R: PartResult + Send,
+    F: Fn(I) -> R + Send,
+{
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let bench = options.bench;
+    let hook_part_str = part_str.clone();
+    let outcome = run_guarded(
+        move || {
+            run_timed(func, input, bench.as_ref(), |result| {
+                print_result(&result.to_outcome().ok().flatten(), &hook_part_str, "");
+            })
+        },
+        options.part_timeout,
+        |failure| {
+            print_failure(failure, &part_str);
+            emit_timeout(day, part, failure);
+        },
+    );
+
+    // NOTE: a returned error is a failure like a panic, so that its samples are not counted either.
+    let outcome = outcome.and_then(|(result, samples, memory)| match result.to_outcome() {
+        Ok(result) => Ok((result, samples, memory)),
+        Err(message) => Err(Failure::Errored(message)),
+    });
+
+    let (result, samples, memory, status, error) = match outcome {
+        Ok((result, samples, memory)) => {
+            let details = format!("{}{}", format_duration(&samples), format_memory(memory));
+            print_result(&result, &part_str, &details);
+            let status = if result.is_some() {
+                PartStatus::Solved
+            } else {
+                PartStatus::Unsolved
+            };
+            (result, samples, memory, status, None)
+        }
+        Err(failure) => {
+            print_failure(&failure, &part_str);
+            let error = Some(failure.to_string());
+            (None, vec![], None, failure.status(), error)
+        }
+    };
+
+    let report = PartReport {
+        day,
+        part,
+        status,
+        answer: result.as_ref().map(Answer::text),
+        error,
+        samples,
+        memory,
+    };
+    report::emit(&report);
+
+    if let Some(result) = result
+        && options.submit == Some(part)
+    {
+        match submit_result(&result, year, day, part) {
+            Ok(outcome) => {
+                println!("{outcome}");
+                if outcome == SubmissionOutcome::Correct {
+                    match answers::store(year, day, part, &result) {
+                        Ok(()) => println!("Stored answer for day {day} part {part}."),
+                        Err(e) => eprintln!("Failed to store answer: {e}"),
+                    }
+                }
+            }
+            Err(e) => eprintln!("failed to submit result: {e}"),
+        }
+    }
+
+    report
+}
+
+/// Run the shared parse step of a solution, its output is passed to every part.
+/// Timed and guarded like a part and reported as [`PARSE_PART`], `None` if parsing failed.
+pub fn run_parse<I, P, F>(
+    func: F,
+    input: I,
+    day: Day,
+    options: &RunOptions,
+) -> (Option<P>, PartReport)
+where
+    I: Copy + Send,
+    P: Send,
+    F: Fn(I) -> P + Send,
+{
+    let bench = options.bench;
+    let outcome = run_guarded(
+        move || run_timed(func, input, bench.as_ref(), |_| print!("Parse:")),
+        options.part_timeout,
+        |failure| {
+            print_failure(failure, "Parse");
+            emit_timeout(day, PARSE_PART, failure);
+        },
+    );
+
+    let (parsed, samples, memory, status, error) = match outcome {
+        Ok((parsed, samples, memory)) => {
+            print!("\r");
+            println!(
+                "Parse:{}{}",
+                format_duration(&samples),
+                format_memory(memory)
+            );
+            (Some(parsed), samples, memory, PartStatus::Solved, None)
+        }
+        Err(failure) => {
+            print_failure(&failure, "Parse");
+            let error = Some(failure.to_string());
+            (None, vec![], None, failure.status(), error)
+        }
+    };
+
+    let report = PartReport {
+        day,
+        part: PARSE_PART,
+        status,
+        answer: None,
+        error,
+        samples,
+        memory,
+    };
+    report::emit(&report);
+
+    (parsed, report)
+}
+
+/// The report of a part that failed to run.
+fn failure_report(day: Day, part: u8, failure: &Failure) -> PartReport {
+    PartReport {
+        day,
+        part,
+        status: failure.status(),
+        answer: None,
+        error: Some(failure.to_string()),
+        samples: vec![],
+        memory: None,
+    }
+}
+
+/// Report a part that timed out, along with the declared parts after it that will not run as the process exits.
+fn emit_timeout(day: Day, part: u8, failure: &Failure) {
+    for report in timeout_reports(day, part, report::declared_parts(), failure) {
+        report::emit(&report);
+    }
+}
+
+/// The reports of a part that timed out and of the parts that come after it in `parts`, see [`report::declare_parts`].
+fn timeout_reports(day: Day, part: u8, parts: &[u8], failure: &Failure) -> Vec<PartReport> {
+    let remaining = parts
+        .iter()
+        .position(|p| *p == part)
+        .map_or(&[][..], |index| &parts[index + 1..]);
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let mut reports = vec![failure_report(day, part, failure)];
+    reports.extend(remaining.iter().map(|remaining| PartReport {
+        error: Some(format!("not run, {} {failure}", part_label(part))),
+        ..failure_report(day, *remaining, failure)
+    }));
+    reports
+}
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+fn part_label(part: u8) -> String {
+    if part == PARSE_PART {
+        "the parse step".into()
+    } else {
+        format!("part {part}")
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Run a solution part. The behavior differs depending on whether a bench config is passed:
+///  1. without a config, the function is executed once.
+///  2. with a config, the function is warmed up and then benched (approx. the configured time budget, clamped to the configured sample counts.)
+///
+/// The heap usage of the first run is counted with the `count-alloc` feature, see [`alloc`].
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
+    bench_config: Option<&BenchConfig>,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Vec<Duration>, Option<Memory>) {
     let timer = Instant::now();
-    let result = {
+    let (result, memory) = {
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        alloc::measure(|| func(input))
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
+    let samples = match bench_config {
+        Some(config) => bench(func, input, &base_time, config),
+        None => vec![base_time],
     };
 
-    (result, run.0, run.1)
+    (result, samples, memory)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    config: &BenchConfig,
+) -> Vec<Duration> {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    // NOTE: the initial run counts towards the warm-up.
+    let warmup_start = Instant::now();
+    while warmup_start.elapsed() + *base_time < config.warmup {
+        black_box(func(black_box(input)));
+    }
+
+    let base_nanos = cmp::max(base_time.as_nanos(), 10);
+    let bench_iterations = (config.budget.as_nanos() / base_nanos).clamp(
+        u128::from(config.min_samples),
+        u128::from(config.max_samples.max(config.min_samples)),
+    );
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -67,36 +495,38 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    timers
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn format_duration(samples: &[Duration]) -> String {
+    match (samples, Stats::from_samples(samples)) {
+        ([duration], _) => format!(" ({duration:.1?})"),
+        (_, Some(stats)) => format!(
+            " ({:.1?} @ {} samples | {})",
+            stats.median(),
+            stats.samples,
+            stats.summary()
+        ),
+        (_, None) => String::new(),
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
-    }
+fn format_memory(memory: Option<Memory>) -> String {
+    memory.map_or_else(String::new, |memory| format!(" [{memory}]"))
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(result: &Option<Answer>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
         Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+            if result.is_multiline() {
+                // letters that could be read from the drawing are shown in front of it.
+                let letters = result
+                    .submittable()
+                    .map(|letters| format!("{ANSI_BOLD}{letters}{ANSI_RESET} "))
+                    .unwrap_or_default();
+                let str = format!("{part}: {letters}▼ {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -125,43 +555,67 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
+fn print_failure(failure: &Failure, part: &str) {
+    print!("\r");
+    println!("{part}: ✖ {ANSI_ITALIC}{failure}{ANSI_RESET}             ");
+}
+
+/// Submit one part of the solution to the Advent of Code website, see [`submissions::guarded_submit`].
+fn submit_result(
+    result: &Answer,
+    year: Year,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+) -> Result<SubmissionOutcome, SubmitError> {
+    let client = AocClient::from_env(year)?;
+    println!("Submitting result...");
+    submissions::guarded_submit(&client, year, day, part, result)
+}
 
-    if !args.contains(&"--submit".into()) {
-        return None;
-    }
+/* -------------------------------------------------------------------------- */
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    }
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    use super::{Failure, PARSE_PART, PartStatus, timeout_reports};
+    use crate::day;
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    };
-
-    if part_submit != part {
-        return None;
+    #[test]
+    fn reports_the_remaining_parts_as_timed_out() {
+        let failure = Failure::TimedOut(Duration::from_secs(5));
+        let reports = timeout_reports(day!(1), PARSE_PART, &[PARSE_PART, 1, 2], &failure);
+        let parts: Vec<_> = reports.iter().map(|r| (r.part, r.status)).collect();
+        assert_eq!(
+            parts,
+            vec![
+                (PARSE_PART, PartStatus::TimedOut),
+                (1, PartStatus::TimedOut),
+                (2, PartStatus::TimedOut)
+            ]
+        );
+        assert_eq!(reports[0].error.as_deref(), Some("timed out after 5s"));
+        assert_eq!(
+            reports[2].error.as_deref(),
+            Some("not run, the parse step timed out after 5s")
+        );
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+    #[test]
+    fn does_not_report_the_parts_before_a_timeout() {
+        let failure = Failure::TimedOut(Duration::from_secs(5));
+        let reports = timeout_reports(day!(1), 1, &[1, 2], &failure);
+        let parts: Vec<_> = reports.iter().map(|r| r.part).collect();
+        assert_eq!(parts, vec![1, 2]);
+        assert_eq!(
+            reports[1].error.as_deref(),
+            Some("not run, part 1 timed out after 5s")
         );
-        process::exit(1);
-    }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+        let reports = timeout_reports(day!(1), 2, &[1, 2], &failure);
+        assert_eq!(reports.len(), 1);
+
+        let reports = timeout_reports(day!(1), 1, &[], &failure);
+        assert_eq!(reports.len(), 1);
+    }
 }
@@ -0,0 +1,243 @@
+/// Module that parses day selection expressions of `time` and `all`, e.g. `1-5,8,10-`, `unsolved` or `slowest:3`.
+/// Selections are resolved against the days of a year and its stored timings.
+use std::{collections::HashSet, error::Error, fmt::Display, str::FromStr};
+
+use crate::template::{Day, Year, timings::Timings};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Term {
+    /// A single day, e.g. `8`.
+    Day(Day),
+    /// A range of days, open ranges end at the last day of the year, e.g. `1-5` or `10-`.
+    Range(Day, Option<Day>),
+    /// Days without a stored timing for both parts.
+    Unsolved,
+    /// The days with the highest stored total time.
+    Slowest(usize),
+}
+
+/// A comma-separated list of days, ranges and filters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DaySelection {
+    terms: Vec<Term>,
+}
+
+impl DaySelection {
+    /// Every day of the year, i.e. `1-`.
+    pub fn all() -> Self {
+        DaySelection {
+            terms: vec![Term::Range(crate::day!(1), None)],
+        }
+    }
+
+    /// Days without a stored timing for both parts, i.e. `unsolved`.
+    pub fn unsolved() -> Self {
+        DaySelection {
+            terms: vec![Term::Unsolved],
+        }
+    }
+
+    /// Whether the selection names the day on its own, e.g. `8` rather than `1-10`.
+    pub fn names(&self, day: Day) -> bool {
+        self.terms.contains(&Term::Day(day))
+    }
+
+    /// Resolve the selection to the days of `year` it contains.
+    /// Fails if a day that is not part of the year is selected explicitly.
+    pub fn resolve(&self, year: Year, timings: &Timings) -> Result<HashSet<Day>, String> {
+        let mut days = HashSet::new();
+
+        for term in &self.terms {
+            match term {
+                Term::Day(day) => {
+                    check_day(year, *day)?;
+                    days.insert(*day);
+                }
+                Term::Range(start, end) => {
+                    check_day(year, *start)?;
+                    let end = end.unwrap_or(year.last_day());
+                    check_day(year, end)?;
+                    days.extend(year.days().filter(|day| *day >= *start && *day <= end));
+                }
+                Term::Unsolved => {
+                    days.extend(year.days().filter(|day| !timings.is_day_complete(*day)));
+                }
+                Term::Slowest(count) => {
+                    let mut slowest: Vec<_> = timings
+                        .data
+                        .iter()
+                        .filter(|timing| year.contains(timing.day))
+                        .collect();
+                    slowest.sort_unstable_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+                    days.extend(slowest.iter().take(*count).map(|timing| timing.day));
+                }
+            }
+        }
+
+        Ok(days)
+    }
+}
+
+fn check_day(year: Year, day: Day) -> Result<(), String> {
+    if year.contains(day) {
+        Ok(())
+    } else {
+        Err(format!(
+            "day {} is not part of {year}, which has {} days",
+            day.into_inner(),
+            year.last_day().into_inner()
+        ))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for DaySelection {
+    type Err = SelectionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let terms = s
+            .split(',')
+            .map(|term| {
+                let term = term.trim();
+                let error = || SelectionFromStrError(term.to_string());
+
+                match term {
+                    "unsolved" => Ok(Term::Unsolved),
+                    _ if term.starts_with("slowest:") => term["slowest:".len()..]
+                        .parse()
+                        .ok()
+                        .filter(|count| *count > 0)
+                        .map(Term::Slowest)
+                        .ok_or_else(error),
+                    _ => match term.split_once('-') {
+                        Some((start, "")) => {
+                            Ok(Term::Range(start.parse().map_err(|_| error())?, None))
+                        }
+                        Some((start, end)) => {
+                            let start: Day = start.parse().map_err(|_| error())?;
+                            let end: Day = end.parse().map_err(|_| error())?;
+                            if start > end {
+                                return Err(error());
+                            }
+                            Ok(Term::Range(start, Some(end)))
+                        }
+                        None => term.parse().map(Term::Day).map_err(|_| error()),
+                    },
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(DaySelection { terms })
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySelection`], contains the invalid term.
+#[derive(Debug)]
+pub struct SelectionFromStrError(String);
+
+impl Error for SelectionFromStrError {}
+
+impl Display for SelectionFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid day selection `{}`, expecting e.g. `1-5,8,10-`, `unsolved` or `slowest:3`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{collections::HashSet, str::FromStr};
+
+    use super::DaySelection;
+    use crate::{
+        day,
+        template::{
+            Day, fixtures,
+            timings::{Timing, Timings},
+        },
+        year,
+    };
+
+    fn timing(day: Day, total_nanos: f64, complete: bool) -> Timing {
+        Timing {
+            part_1: Some("1ms".into()),
+            part_2: complete.then(|| "1ms".into()),
+            total_nanos,
+            ..fixtures::timing(day)
+        }
+    }
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                timing(day!(1), 100.0, true),
+                timing(day!(2), 300.0, true),
+                timing(day!(3), 200.0, false),
+                timing(day!(4), 50.0, true),
+            ],
+            ..Timings::default()
+        }
+    }
+
+    fn resolve(s: &str) -> Result<Vec<u8>, String> {
+        let days = DaySelection::from_str(s)
+            .map_err(|e| e.to_string())?
+            .resolve(year!(2025), &get_mock_timings())?;
+        let mut days: Vec<u8> = days.into_iter().map(Day::into_inner).collect();
+        days.sort_unstable();
+        Ok(days)
+    }
+
+    #[test]
+    fn resolves_days_and_ranges() {
+        assert_eq!(resolve("8"), Ok(vec![8]));
+        assert_eq!(resolve("1-3,8"), Ok(vec![1, 2, 3, 8]));
+        assert_eq!(resolve("1-5,8,10-"), Ok(vec![1, 2, 3, 4, 5, 8, 10, 11, 12]));
+        assert_eq!(resolve(" 2 , 2-3 "), Ok(vec![2, 3]));
+    }
+
+    #[test]
+    fn resolves_filters() {
+        assert_eq!(resolve("unsolved"), Ok(vec![3, 5, 6, 7, 8, 9, 10, 11, 12]));
+        assert_eq!(resolve("slowest:2"), Ok(vec![2, 3]));
+        assert_eq!(resolve("slowest:2,12"), Ok(vec![2, 3, 12]));
+        assert_eq!(resolve("slowest:10"), Ok(vec![1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn resolves_per_year() {
+        let days = DaySelection::from_str("20-")
+            .unwrap()
+            .resolve(year!(2024), &Timings::default())
+            .unwrap();
+        assert_eq!(days, (20..=25).filter_map(Day::new).collect::<HashSet<_>>());
+        assert_eq!(resolve("13").is_err(), true);
+        assert_eq!(resolve("10-13").is_err(), true);
+    }
+
+    #[test]
+    fn names_single_days() {
+        let selection = DaySelection::from_str("1-5,8").unwrap();
+        assert_eq!(selection.names(day!(8)), true);
+        assert_eq!(selection.names(day!(3)), false);
+        assert_eq!(DaySelection::all().names(day!(1)), false);
+    }
+
+    #[test]
+    fn rejects_invalid_selections() {
+        assert_eq!(resolve("").is_err(), true);
+        assert_eq!(resolve("0").is_err(), true);
+        assert_eq!(resolve("5-3").is_err(), true);
+        assert_eq!(resolve("1,,2").is_err(), true);
+        assert_eq!(resolve("slowest").is_err(), true);
+        assert_eq!(resolve("slowest:x").is_err(), true);
+        assert_eq!(resolve("slowest:0").is_err(), true);
+        assert_eq!(resolve("solved").is_err(), true);
+    }
+}
@@ -0,0 +1,219 @@
+/// Summary statistics over benchmark samples.
+use std::{collections::HashMap, time::Duration};
+
+use tinyjson::JsonValue;
+
+/// Two-sided 95% critical values of Student's t-distribution for 1 to 30 degrees of freedom.
+const T_TABLE_95: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+    2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+    2.052, 2.048, 2.045, 2.042,
+];
+
+/// Critical value of the normal distribution, used above 30 degrees of freedom.
+const Z_95: f64 = 1.96;
+
+/// Statistics of the samples of a benched part, computed after outlier rejection.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub mean_nanos: f64,
+    pub median_nanos: f64,
+    pub min_nanos: f64,
+    pub max_nanos: f64,
+    pub stddev_nanos: f64,
+    /// Half-width of the 95% confidence interval of the mean.
+    pub ci95_nanos: f64,
+    /// Number of samples the statistics are computed from.
+    pub samples: usize,
+    /// Number of samples that were rejected as outliers.
+    pub outliers: usize,
+}
+
+impl Stats {
+    /// Compute statistics over `samples`, rejecting samples outside of Tukey's fences (1.5 × IQR).
+    /// Returns `None` if there are no samples.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut sorted: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        sorted.sort_unstable_by(f64::total_cmp);
+
+        if sorted.is_empty() {
+            return None;
+        }
+
+        let q1 = quantile(&sorted, 0.25);
+        let q3 = quantile(&sorted, 0.75);
+        let fence = 1.5 * (q3 - q1);
+
+        let kept: Vec<f64> = sorted
+            .iter()
+            .copied()
+            .filter(|x| *x >= q1 - fence && *x <= q3 + fence)
+            .collect();
+
+        let n = kept.len() as f64;
+        let mean = kept.iter().sum::<f64>() / n;
+        let stddev = if kept.len() > 1 {
+            (kept.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+        } else {
+            0.0
+        };
+        let critical = T_TABLE_95.get(kept.len().saturating_sub(2)).copied();
+
+        Some(Stats {
+            mean_nanos: mean,
+            median_nanos: quantile(&kept, 0.5),
+            min_nanos: kept[0],
+            max_nanos: kept[kept.len() - 1],
+            stddev_nanos: stddev,
+            ci95_nanos: critical.unwrap_or(Z_95) * stddev / n.sqrt(),
+            samples: kept.len(),
+            outliers: sorted.len() - kept.len(),
+        })
+    }
+
+    /// The median, which is used as the headline time of a part.
+    pub fn median(&self) -> Duration {
+        to_duration(self.median_nanos)
+    }
+
+    /// Human-readable summary of the spread of the samples, e.g. for printing next to a result.
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "mean {:.1?} ± {:.1?}, σ {:.1?}, min {:.1?}, max {:.1?}",
+            to_duration(self.mean_nanos),
+            to_duration(self.ci95_nanos),
+            to_duration(self.stddev_nanos),
+            to_duration(self.min_nanos),
+            to_duration(self.max_nanos),
+        );
+        if self.outliers > 0 {
+            summary.push_str(&format!(", {} outliers", self.outliers));
+        }
+        summary
+    }
+}
+
+/// Linearly interpolated quantile of sorted, non-empty values.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let lower = pos.floor() as usize;
+    let upper = pos.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (pos - lower as f64)
+}
+
+/// Converts nanoseconds as stored in [`Stats`] back to a duration.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn to_duration(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("mean_nanos".into(), JsonValue::Number(value.mean_nanos));
+        map.insert("median_nanos".into(), JsonValue::Number(value.median_nanos));
+        map.insert("min_nanos".into(), JsonValue::Number(value.min_nanos));
+        map.insert("max_nanos".into(), JsonValue::Number(value.max_nanos));
+        map.insert("stddev_nanos".into(), JsonValue::Number(value.stddev_nanos));
+        map.insert("ci95_nanos".into(), JsonValue::Number(value.ci95_nanos));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        Ok(Stats {
+            mean_nanos: number("mean_nanos")?,
+            median_nanos: number("median_nanos")?,
+            min_nanos: number("min_nanos")?,
+            max_nanos: number("max_nanos")?,
+            stddev_nanos: number("stddev_nanos")?,
+            ci95_nanos: number("ci95_nanos")?,
+            samples: number("samples")? as usize,
+            outliers: number("outliers")? as usize,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::Stats;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|n| Duration::from_nanos(*n)).collect()
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = Stats::from_samples(&nanos(&[42])).unwrap();
+        assert_eq!(stats.median(), Duration::from_nanos(42));
+        assert_eq!(stats.stddev_nanos, 0.0);
+        assert_eq!(stats.ci95_nanos, 0.0);
+        assert_eq!(stats.samples, 1);
+    }
+
+    #[test]
+    fn computes_statistics() {
+        let stats = Stats::from_samples(&nanos(&[40, 10, 30, 20])).unwrap();
+        assert_eq!(stats.mean_nanos, 25.0);
+        assert_eq!(stats.median_nanos, 25.0);
+        assert_eq!(stats.min_nanos, 10.0);
+        assert_eq!(stats.max_nanos, 40.0);
+        assert!((stats.stddev_nanos - 12.909_944).abs() < 1e-6);
+        assert!((stats.ci95_nanos - 3.182 * 12.909_944 / 2.0).abs() < 1e-5);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = Stats::from_samples(&nanos(&[10, 11, 12, 11, 10, 12, 11, 500])).unwrap();
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.samples, 7);
+        assert_eq!(stats.max_nanos, 12.0);
+        assert_eq!(stats.median_nanos, 11.0);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = Stats::from_samples(&nanos(&[40, 10, 30, 20])).unwrap();
+        let parsed = Stats::try_from(&JsonValue::from(&stats)).unwrap();
+        assert_eq!(parsed, stats);
+    }
+}
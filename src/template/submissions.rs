@@ -0,0 +1,468 @@
+/// Module that guards submissions with a local log of every answer submitted for a part.
+/// Each part has its own JSON lines file, e.g. `data/2025/submissions/01-2.jsonl` for the second part of day 1 in 2025.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    str::FromStr,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{
+    Day, Year,
+    answer::Answer,
+    aoc_client::{AocClient, AocClientError, SubmissionOutcome},
+    data_dir,
+};
+
+/// Cooldown after a wrong answer. The website asks for longer waits after repeated wrong answers.
+const WRONG_ANSWER_COOLDOWN: Duration = Duration::from_secs(60);
+const REPEATED_WRONG_ANSWER_COOLDOWN: Duration = Duration::from_secs(5 * 60);
+const REPEATED_WRONG_ANSWER_COUNT: usize = 4;
+
+/// A single submitted answer and the verdict it received.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub answer: String,
+    pub outcome: SubmissionOutcome,
+}
+
+/// Reasons for refusing to submit an answer.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Refusal {
+    AlreadySolved {
+        answer: String,
+    },
+    Duplicate {
+        outcome: SubmissionOutcome,
+    },
+    TooHigh {
+        known_too_high: i128,
+    },
+    TooLow {
+        known_too_low: i128,
+    },
+    /// Grids, e.g. letters drawn as ASCII art, have to be read before they can be submitted.
+    NotSubmittable,
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved { answer } => {
+                write!(f, "this part was already solved with answer {answer}.")
+            }
+            Refusal::Duplicate { outcome } => {
+                write!(f, "this answer was submitted before: {outcome}")
+            }
+            Refusal::TooHigh { known_too_high } => {
+                write!(f, "{known_too_high} was already too high.")
+            }
+            Refusal::TooLow { known_too_low } => write!(f, "{known_too_low} was already too low."),
+            Refusal::NotSubmittable => {
+                write!(f, "this answer has to be read and submitted by hand.")
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Refused(Refusal),
+    Client(AocClientError),
+    IO(io::Error),
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitError::Refused(refusal) => write!(f, "refusing to submit, {refusal}"),
+            SubmitError::Client(e) => write!(f, "{e}"),
+            SubmitError::IO(e) => write!(f, "could not update submission log: {e}"),
+        }
+    }
+}
+
+impl From<AocClientError> for SubmitError {
+    fn from(e: AocClientError) -> Self {
+        SubmitError::Client(e)
+    }
+}
+
+impl From<io::Error> for SubmitError {
+    fn from(e: io::Error) -> Self {
+        SubmitError::IO(e)
+    }
+}
+
+/// All answers submitted for a part, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct SubmissionLog {
+    pub entries: Vec<Submission>,
+}
+
+impl SubmissionLog {
+    /// Read the log of a part. If not present, returns an empty log.
+    pub fn read(year: Year, day: Day, part: u8) -> Result<Self, String> {
+        match fs::read_to_string(get_log_path(year, day, part)) {
+            Ok(contents) => SubmissionLog::from_str(&contents),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(SubmissionLog::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Append a submission to the log file of a part.
+    pub fn append(
+        year: Year,
+        day: Day,
+        part: u8,
+        submission: &Submission,
+    ) -> Result<(), io::Error> {
+        let path = get_log_path(year, day, part);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let line = JsonValue::from(submission)
+            .stringify()
+            .map_err(|e| io::Error::other(e.to_string()))?;
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{line}")
+    }
+
+    /// Check whether submitting `answer` can possibly be accepted given the previous verdicts.
+    pub fn check(&self, answer: &str) -> Result<(), Refusal> {
+        let answer = answer.trim();
+
+        if let Some(correct) = self
+            .entries
+            .iter()
+            .find(|s| s.outcome == SubmissionOutcome::Correct)
+        {
+            return Err(Refusal::AlreadySolved {
+                answer: correct.answer.clone(),
+            });
+        }
+
+        if let Some(previous) = self
+            .entries
+            .iter()
+            .filter(|s| is_verdict(&s.outcome))
+            .find(|s| s.answer.trim() == answer)
+        {
+            return Err(Refusal::Duplicate {
+                outcome: previous.outcome.clone(),
+            });
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let known = |outcome: SubmissionOutcome| {
+            self.entries
+                .iter()
+                .filter(move |s| s.outcome == outcome)
+                .filter_map(|s| s.answer.trim().parse::<i128>().ok())
+        };
+
+        if let Some(known_too_high) = known(SubmissionOutcome::TooHigh).min()
+            && value >= known_too_high
+        {
+            return Err(Refusal::TooHigh { known_too_high });
+        }
+
+        if let Some(known_too_low) = known(SubmissionOutcome::TooLow).max()
+            && value <= known_too_low
+        {
+            return Err(Refusal::TooLow { known_too_low });
+        }
+
+        Ok(())
+    }
+
+    /// Remaining cooldown at `now` (seconds since the unix epoch) caused by the last submission.
+    pub fn cooldown_remaining(&self, now: u64) -> Option<Duration> {
+        let last = self.entries.last()?;
+
+        let cooldown = match &last.outcome {
+            SubmissionOutcome::RateLimited { wait } => *wait,
+            SubmissionOutcome::Incorrect
+            | SubmissionOutcome::TooHigh
+            | SubmissionOutcome::TooLow => {
+                let wrong_answers = self
+                    .entries
+                    .iter()
+                    .filter(|s| is_verdict(&s.outcome))
+                    .count();
+                if wrong_answers >= REPEATED_WRONG_ANSWER_COUNT {
+                    REPEATED_WRONG_ANSWER_COOLDOWN
+                } else {
+                    WRONG_ANSWER_COOLDOWN
+                }
+            }
+            SubmissionOutcome::Correct | SubmissionOutcome::AlreadySolved => return None,
+        };
+
+        let elapsed = Duration::from_secs(now.saturating_sub(last.timestamp));
+        cooldown.checked_sub(elapsed).filter(|d| !d.is_zero())
+    }
+}
+
+/// Whether the outcome is a verdict on the answer itself, as opposed to the answer not being checked.
+fn is_verdict(outcome: &SubmissionOutcome) -> bool {
+    !matches!(
+        outcome,
+        SubmissionOutcome::AlreadySolved | SubmissionOutcome::RateLimited { .. }
+    )
+}
+
+fn get_log_path(year: Year, day: Day, part: u8) -> PathBuf {
+    data_dir(year)
+        .join("submissions")
+        .join(format!("{day}-{part}.jsonl"))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Submit an answer unless the submission log shows that it cannot be correct.
+/// Waits for any remaining cooldown before submitting and records the verdict in the log.
+pub fn guarded_submit(
+    client: &AocClient,
+    year: Year,
+    day: Day,
+    part: u8,
+    answer: &Answer,
+) -> Result<SubmissionOutcome, SubmitError> {
+    let answer = answer
+        .submittable()
+        .ok_or(SubmitError::Refused(Refusal::NotSubmittable))?;
+    let answer = answer.as_str();
+
+    let log =
+        SubmissionLog::read(year, day, part).map_err(|e| SubmitError::IO(io::Error::other(e)))?;
+    log.check(answer).map_err(SubmitError::Refused)?;
+
+    if let Some(wait) = log.cooldown_remaining(now()) {
+        println!("Waiting {}s for the submission cooldown...", wait.as_secs());
+        thread::sleep(wait);
+    }
+
+    let outcome = client.submit(day, part, answer)?;
+
+    SubmissionLog::append(
+        year,
+        day,
+        part,
+        &Submission {
+            timestamp: now(),
+            answer: answer.trim().to_string(),
+            outcome: outcome.clone(),
+        },
+    )?;
+
+    Ok(outcome)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for SubmissionLog {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let entries = s
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let json = JsonValue::from_str(line).or(Err("not valid JSON line."))?;
+                Submission::try_from(&json)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(SubmissionLog { entries })
+    }
+}
+
+impl From<&Submission> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let outcome = match value.outcome {
+            SubmissionOutcome::Correct => "correct",
+            SubmissionOutcome::Incorrect => "incorrect",
+            SubmissionOutcome::TooHigh => "too_high",
+            SubmissionOutcome::TooLow => "too_low",
+            SubmissionOutcome::AlreadySolved => "already_solved",
+            SubmissionOutcome::RateLimited { wait } => {
+                map.insert("wait_secs".into(), JsonValue::Number(wait.as_secs() as f64));
+                "rate_limited"
+            }
+        };
+
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert("outcome".into(), JsonValue::String(outcome.into()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let outcome = match json
+            .get("outcome")
+            .and_then(|v| v.get::<String>())
+            .map(String::as_str)
+        {
+            Some("correct") => SubmissionOutcome::Correct,
+            Some("incorrect") => SubmissionOutcome::Incorrect,
+            Some("too_high") => SubmissionOutcome::TooHigh,
+            Some("too_low") => SubmissionOutcome::TooLow,
+            Some("already_solved") => SubmissionOutcome::AlreadySolved,
+            Some("rate_limited") => SubmissionOutcome::RateLimited {
+                wait: json
+                    .get("wait_secs")
+                    .and_then(|v| v.get::<f64>())
+                    .map(|v| Duration::from_secs(*v as u64))
+                    .ok_or("Expected submission.wait_secs to be a number.")?,
+            },
+            _ => return Err("Expected submission.outcome to be a known outcome.".into()),
+        };
+
+        Ok(Submission {
+            timestamp,
+            answer: answer.clone(),
+            outcome,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{str::FromStr, time::Duration};
+
+    use tinyjson::JsonValue;
+
+    use super::{Refusal, Submission, SubmissionLog};
+    use crate::template::aoc_client::SubmissionOutcome;
+
+    fn submission(timestamp: u64, answer: &str, outcome: SubmissionOutcome) -> Submission {
+        Submission {
+            timestamp,
+            answer: answer.into(),
+            outcome,
+        }
+    }
+
+    fn get_mock_log() -> SubmissionLog {
+        SubmissionLog {
+            entries: vec![
+                submission(100, "500", SubmissionOutcome::TooHigh),
+                submission(200, "800", SubmissionOutcome::TooHigh),
+                submission(300, "100", SubmissionOutcome::TooLow),
+                submission(
+                    310,
+                    "300",
+                    SubmissionOutcome::RateLimited {
+                        wait: Duration::from_secs(50),
+                    },
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn roundtrips_json_lines() {
+        let log = get_mock_log();
+        let lines: Vec<String> = log
+            .entries
+            .iter()
+            .map(|s| JsonValue::from(s).stringify().unwrap())
+            .collect();
+        let parsed = SubmissionLog::from_str(&lines.join("\n")).unwrap();
+        assert_eq!(parsed.entries, log.entries);
+    }
+
+    #[test]
+    fn refuses_answers_outside_bracket() {
+        let log = get_mock_log();
+        assert_eq!(
+            log.check("500"),
+            Err(Refusal::Duplicate {
+                outcome: SubmissionOutcome::TooHigh
+            })
+        );
+        assert_eq!(
+            log.check("600"),
+            Err(Refusal::TooHigh {
+                known_too_high: 500
+            })
+        );
+        assert_eq!(log.check("-3"), Err(Refusal::TooLow { known_too_low: 100 }));
+        assert_eq!(log.check("300"), Ok(()));
+        assert_eq!(log.check("499"), Ok(()));
+        assert_eq!(log.check("ABCDEF"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let mut log = get_mock_log();
+        log.entries
+            .push(submission(400, "300", SubmissionOutcome::Correct));
+        assert_eq!(
+            log.check("301"),
+            Err(Refusal::AlreadySolved {
+                answer: "300".into()
+            })
+        );
+    }
+
+    #[test]
+    fn computes_remaining_cooldown() {
+        let mut log = get_mock_log();
+        assert_eq!(log.cooldown_remaining(320), Some(Duration::from_secs(40)));
+        assert_eq!(log.cooldown_remaining(360), None);
+
+        log.entries
+            .push(submission(400, "200", SubmissionOutcome::Incorrect));
+        assert_eq!(log.cooldown_remaining(430), Some(Duration::from_secs(270)));
+
+        assert_eq!(SubmissionLog::default().cooldown_remaining(0), None);
+    }
+}
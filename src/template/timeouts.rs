@@ -0,0 +1,150 @@
+/// Module that parses the wall-clock budgets of isolated solution runs, e.g. `60` or `60,12=300`.
+/// A plain number of seconds applies to every day, `<day>=<secs>` overrides the budget of a single day.
+use std::{collections::HashMap, error::Error, fmt::Display, str::FromStr, time::Duration};
+
+use crate::template::Day;
+
+/// A default budget for every day plus per-day overrides.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DayTimeouts {
+    default: Option<Duration>,
+    overrides: HashMap<Day, Duration>,
+}
+
+impl DayTimeouts {
+    /// The budget of a day, `None` if the day may run for as long as it takes.
+    pub fn get(&self, day: Day) -> Option<Duration> {
+        self.overrides.get(&day).copied().or(self.default)
+    }
+
+    /// Adds budgets for days that have no override yet, e.g. the per-day timeouts of `aoc.toml`.
+    /// A budget of a single day is more specific than the default, so the fallbacks take precedence over it.
+    #[must_use]
+    pub fn with_fallbacks(mut self, fallbacks: impl IntoIterator<Item = (Day, Duration)>) -> Self {
+        for (day, timeout) in fallbacks {
+            self.overrides.entry(day).or_insert(timeout);
+        }
+        self
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn parse_secs(s: &str) -> Option<Duration> {
+    let secs: f64 = s.trim().parse().ok()?;
+    Duration::try_from_secs_f64(secs)
+        .ok()
+        .filter(|timeout| !timeout.is_zero())
+}
+
+impl FromStr for DayTimeouts {
+    type Err = TimeoutsFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut timeouts = DayTimeouts::default();
+
+        for term in s.split(',').map(str::trim) {
+            let error = || TimeoutsFromStrError(term.to_string());
+
+            match term.split_once('=') {
+                Some((day, secs)) => {
+                    let day: Day = day.trim().parse().map_err(|_| error())?;
+                    let timeout = parse_secs(secs).ok_or_else(error)?;
+                    timeouts.overrides.insert(day, timeout);
+                }
+                None => timeouts.default = Some(parse_secs(term).ok_or_else(error)?),
+            }
+        }
+
+        Ok(timeouts)
+    }
+}
+
+/// An error which can be returned when parsing [`DayTimeouts`], contains the invalid term.
+#[derive(Debug)]
+pub struct TimeoutsFromStrError(String);
+
+impl Error for TimeoutsFromStrError {}
+
+impl Display for TimeoutsFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid timeout `{}`, expecting seconds per day with per-day overrides, e.g. `60,12=300`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{str::FromStr, time::Duration};
+
+    use super::DayTimeouts;
+    use crate::day;
+
+    #[test]
+    fn parses_default_and_overrides() {
+        let timeouts = DayTimeouts::from_str("60, 12=300,3=0.5").unwrap();
+        assert_eq!(timeouts.get(day!(1)), Some(Duration::from_secs(60)));
+        assert_eq!(timeouts.get(day!(12)), Some(Duration::from_secs(300)));
+        assert_eq!(timeouts.get(day!(3)), Some(Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn handles_overrides_without_default() {
+        let timeouts = DayTimeouts::from_str("12=300").unwrap();
+        assert_eq!(timeouts.get(day!(12)), Some(Duration::from_secs(300)));
+        assert_eq!(timeouts.get(day!(1)), None);
+        assert_eq!(DayTimeouts::default().get(day!(1)), None);
+    }
+
+    #[test]
+    fn prefers_overrides_to_fallbacks() {
+        let fallbacks = [
+            (day!(3), Duration::from_secs(5)),
+            (day!(12), Duration::from_secs(5)),
+        ];
+        let timeouts = DayTimeouts::from_str("60,12=300")
+            .unwrap()
+            .with_fallbacks(fallbacks);
+        assert_eq!(timeouts.get(day!(12)), Some(Duration::from_secs(300)));
+        assert_eq!(timeouts.get(day!(3)), Some(Duration::from_secs(5)));
+        assert_eq!(timeouts.get(day!(1)), Some(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn prefers_fallbacks_to_the_default() {
+        let fallbacks = [(day!(12), Duration::from_secs(300))];
+        let timeouts = DayTimeouts::from_str("60")
+            .unwrap()
+            .with_fallbacks(fallbacks);
+        assert_eq!(timeouts.get(day!(12)), Some(Duration::from_secs(300)));
+        assert_eq!(timeouts.get(day!(1)), Some(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn falls_back_for_days_without_a_budget() {
+        let fallbacks = [(day!(3), Duration::from_secs(5))];
+        let timeouts = DayTimeouts::from_str("12=300")
+            .unwrap()
+            .with_fallbacks(fallbacks);
+        assert_eq!(timeouts.get(day!(3)), Some(Duration::from_secs(5)));
+        assert_eq!(timeouts.get(day!(12)), Some(Duration::from_secs(300)));
+        assert_eq!(timeouts.get(day!(1)), None);
+    }
+
+    #[test]
+    fn rejects_invalid_timeouts() {
+        assert_eq!(DayTimeouts::from_str("").is_err(), true);
+        assert_eq!(DayTimeouts::from_str("0").is_err(), true);
+        assert_eq!(DayTimeouts::from_str("-5").is_err(), true);
+        assert_eq!(DayTimeouts::from_str("26=5").is_err(), true);
+        assert_eq!(DayTimeouts::from_str("1e20").is_err(), true);
+        assert_eq!(DayTimeouts::from_str("inf").is_err(), true);
+        assert_eq!(DayTimeouts::from_str("12=").is_err(), true);
+        assert_eq!(DayTimeouts::from_str("60,,12=5").is_err(), true);
+    }
+}
@@ -1,17 +1,83 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, path::PathBuf, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::alloc::Memory;
+use crate::template::environment::Environment;
+use crate::template::report::{PARSE_PART, PartReport, PartStatus};
+use crate::template::stats::Stats;
+use crate::template::{Day, Year, data_dir};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+fn get_timings_path(year: Year) -> PathBuf {
+    data_dir(year).join("timings.json")
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
+    /// Time of the shared parse step, only present for solutions that have one.
+    pub parse: Option<String>,
+    pub parse_stats: Option<Stats>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub part_1_stats: Option<Stats>,
+    pub part_2_stats: Option<Stats>,
+    /// Heap usage of the parts, only present for timings measured with the `count-alloc` feature.
+    pub part_1_memory: Option<Memory>,
+    pub part_2_memory: Option<Memory>,
     pub total_nanos: f64,
+    /// Whether a part or the whole solution ran out of time, see `--part-timeout` and `--timeout`.
+    pub timed_out: bool,
+}
+
+impl Timing {
+    /// Build the timing of a day from the reports its solution binary emitted.
+    /// Only solved parts contribute a time, which is the median of their samples, and their memory usage.
+    pub fn from_reports(day: Day, reports: &[PartReport]) -> Self {
+        let mut timing = Timing {
+            day,
+            parse: None,
+            parse_stats: None,
+            part_1: None,
+            part_2: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            part_1_memory: None,
+            part_2_memory: None,
+            total_nanos: 0_f64,
+            timed_out: reports
+                .iter()
+                .any(|r| r.day == day && r.status == PartStatus::TimedOut),
+        };
+
+        let solved = || {
+            reports
+                .iter()
+                .filter(|r| r.day == day && r.status == PartStatus::Solved)
+        };
+
+        for report in solved() {
+            match report.part {
+                1 => timing.part_1_memory = report.memory,
+                2 => timing.part_2_memory = report.memory,
+                _ => {}
+            }
+        }
+
+        solved()
+            .filter_map(|r| Some((r.part, Stats::from_samples(&r.samples)?)))
+            .for_each(|(part, stats)| {
+                let formatted = Some(format!("{:.1?}", stats.median()));
+                timing.total_nanos += stats.median_nanos;
+                match part {
+                    PARSE_PART => (timing.parse, timing.parse_stats) = (formatted, Some(stats)),
+                    1 => (timing.part_1, timing.part_1_stats) = (formatted, Some(stats)),
+                    _ => (timing.part_2, timing.part_2_stats) = (formatted, Some(stats)),
+                }
+            });
+
+        timing
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -19,25 +85,48 @@ pub struct Timing {
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
     pub data: Vec<Timing>,
+    /// The environment of the most recent run, `None` for timings stored by older versions.
+    pub environment: Option<Environment>,
+    /// Whether some of the timings were measured on a different machine than the most recent run.
+    pub mixed_environments: bool,
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Build timings from the reports of one or more days, see [`Timing::from_reports`].
+    pub fn from_reports(reports: &[PartReport]) -> Self {
+        let mut days: Vec<Day> = reports.iter().map(|r| r.day).collect();
+        days.dedup();
+
+        Timings {
+            data: days
+                .into_iter()
+                .map(|day| Timing::from_reports(day, reports))
+                .collect(),
+            ..Timings::default()
+        }
+    }
+
+    /// Dehydrate the timings of a year to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let path = get_timings_path(year);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::File::create(path)?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+    /// Rehydrate the timings of a year from a JSON file. If not present, returns empty timings.
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(get_timings_path(year))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// The merged timings are mixed if kept timings were measured on another machine than `new`.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
@@ -45,14 +134,27 @@ impl Timings {
             data.push(timing.clone());
         }
 
+        let mut kept = false;
         for timing in &self.data {
             if !data.iter().any(|t| t.day == timing.day) {
                 data.push(timing.clone());
+                kept = true;
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        let environment = new.environment.clone().or_else(|| self.environment.clone());
+        let other_machine = match (&self.environment, &new.environment) {
+            (Some(previous), Some(current)) => !previous.same_machine(current),
+            _ => false,
+        };
+
+        data.sort_unstable_by_key(|a| a.day);
+        Timings {
+            data,
+            environment,
+            mixed_environments: new.mixed_environments
+                || (kept && (self.mixed_environments || other_machine)),
+        }
     }
 
     /// Sum up total duration of timings as millis.
@@ -78,6 +180,14 @@ impl From<Timings> for JsonValue {
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
 
+        if let Some(environment) = &value.environment {
+            map.insert("environment".into(), JsonValue::from(environment));
+        }
+
+        if value.mixed_environments {
+            map.insert("mixed_environments".into(), JsonValue::Boolean(true));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -88,19 +198,38 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
+        // NOTE: the environment is optional so that timings stored by older versions can still be read.
+        let environment = json
+            .get("environment")
+            .map(Environment::try_from)
+            .transpose()?;
+        let mixed_environments = json
+            .get("mixed_environments")
+            .map(|v| {
+                v.get::<bool>()
+                    .copied()
+                    .ok_or("expected `json.mixed_environments` to be a boolean.")
+            })
+            .transpose()?
+            .unwrap_or(false);
+
         Ok(Timings {
             data: json_data
                 .iter()
                 .map(Timing::try_from)
                 .collect::<Result<_, _>>()?,
+            environment,
+            mixed_environments,
         })
     }
 }
@@ -133,6 +262,34 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        if value.timed_out {
+            map.insert("timed_out".into(), JsonValue::Boolean(true));
+        }
+
+        if let Some(parse) = &value.parse {
+            map.insert("parse".into(), JsonValue::String(parse.clone()));
+        }
+
+        if let Some(stats) = &value.parse_stats {
+            map.insert("parse_stats".into(), JsonValue::from(stats));
+        }
+
+        if let Some(stats) = &value.part_1_stats {
+            map.insert("part_1_stats".into(), JsonValue::from(stats));
+        }
+
+        if let Some(stats) = &value.part_2_stats {
+            map.insert("part_2_stats".into(), JsonValue::from(stats));
+        }
+
+        if let Some(memory) = &value.part_1_memory {
+            map.insert("part_1_memory".into(), JsonValue::from(memory));
+        }
+
+        if let Some(memory) = &value.part_2_memory {
+            map.insert("part_2_memory".into(), JsonValue::from(memory));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -166,11 +323,47 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: the parse step, stats, memory usage and time-out flag are optional so that timings stored by older versions can still be read.
+        let parse = json
+            .get("parse")
+            .map(|v| {
+                v.get::<String>()
+                    .ok_or("Expected timing.parse to be a string.")
+            })
+            .transpose()?;
+        let parse_stats = json.get("parse_stats").map(Stats::try_from).transpose()?;
+        let part_1_stats = json.get("part_1_stats").map(Stats::try_from).transpose()?;
+        let part_2_stats = json.get("part_2_stats").map(Stats::try_from).transpose()?;
+        let part_1_memory = json
+            .get("part_1_memory")
+            .map(Memory::try_from)
+            .transpose()?;
+        let part_2_memory = json
+            .get("part_2_memory")
+            .map(Memory::try_from)
+            .transpose()?;
+        let timed_out = json
+            .get("timed_out")
+            .map(|v| {
+                v.get::<bool>()
+                    .copied()
+                    .ok_or("Expected timing.timed_out to be a boolean.")
+            })
+            .transpose()?
+            .unwrap_or(false);
+
         Ok(Timing {
             day,
+            parse: parse.cloned(),
+            parse_stats,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            part_1_stats,
+            part_2_stats,
+            part_1_memory,
+            part_2_memory,
             total_nanos,
+            timed_out,
         })
     }
 }
@@ -179,7 +372,7 @@ impl TryFrom<&JsonValue> for Timing {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use crate::day;
+    use crate::{day, template::fixtures::timing};
 
     use super::{Timing, Timings};
 
@@ -187,24 +380,24 @@ mod tests {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..timing(day!(1))
                 },
                 Timing {
-                    day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..timing(day!(2))
                 },
                 Timing {
-                    day: day!(4),
                     part_1: Some("40ms".into()),
-                    part_2: None,
                     total_nanos: 4e+10,
+                    ..timing(day!(4))
                 },
             ],
+            ..Timings::default()
         }
     }
 
@@ -223,6 +416,35 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "mean_nanos": 1000100, "median_nanos": 1000000, "min_nanos": 900000, "max_nanos": 1200000, "stddev_nanos": 500, "ci95_nanos": 10, "samples": 998, "outliers": 2 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.as_ref().unwrap();
+            assert_eq!(stats.median_nanos, 1_000_000_f64);
+            assert_eq!(stats.samples, 998);
+            assert_eq!(stats.outliers, 2);
+            assert_eq!(timing.part_2_stats, None);
+        }
+
+        #[test]
+        fn handles_json_timings_with_parse() {
+            let json = r#"{ "data": [{ "day": "01", "parse": "2ms", "part_1": "1ms", "part_2": null, "total_nanos": 3000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse, Some("2ms".to_string()));
+            assert_eq!(timing.parse_stats, None);
+        }
+
+        #[test]
+        fn handles_json_timings_with_time_out() {
+            let json = r#"{ "data": [{ "day": "12", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "timed_out": true }, { "day": "01", "part_1": null, "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].timed_out, true);
+            assert_eq!(timings.data[1].timed_out, false);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -271,56 +493,165 @@ mod tests {
     mod is_day_complete {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                fixtures::timing,
+                timings::{Timing, Timings},
+            },
         };
 
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    ..timing(day!(1))
                 }],
+                ..Timings::default()
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
                     part_1: Some("1ms".into()),
-                    part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    ..timing(day!(1))
                 }],
+                ..Timings::default()
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
-                }],
+                data: vec![timing(day!(1))],
+                ..Timings::default()
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod from_reports {
+        use std::time::Duration;
+
+        use crate::{
+            day,
+            template::{
+                alloc::Memory,
+                report::{PARSE_PART, PartReport, PartStatus},
+                timings::Timing,
+            },
+        };
+
+        fn report(part: u8, status: PartStatus, samples: &[u64]) -> PartReport {
+            PartReport {
+                day: day!(1),
+                part,
+                status,
+                answer: Some("1 (2s @ 5 samples)".into()),
+                error: None,
+                samples: samples.iter().map(|n| Duration::from_nanos(*n)).collect(),
+                memory: None,
+            }
+        }
+
+        #[test]
+        fn uses_median_sample_durations() {
+            let timing = Timing::from_reports(
+                day!(1),
+                &[
+                    report(1, PartStatus::Solved, &[70, 74, 78, 1000]),
+                    report(2, PartStatus::Solved, &[74_130_000]),
+                ],
+            );
+            assert_eq!(timing.part_1.unwrap(), "74.0ns");
+            assert_eq!(timing.part_2.unwrap(), "74.1ms");
+            assert_eq!(timing.part_1_stats.unwrap().outliers, 1);
+            assert_eq!(timing.part_2_stats.unwrap().samples, 1);
+            assert_eq!(timing.total_nanos, 74_130_074_f64);
+        }
+
+        #[test]
+        fn handles_unsolved_parts() {
+            let timing = Timing::from_reports(
+                day!(1),
+                &[
+                    report(1, PartStatus::Unsolved, &[100]),
+                    report(2, PartStatus::Solved, &[]),
+                ],
+            );
+            assert_eq!(timing.part_1, None);
+            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.part_1_stats, None);
+            assert_eq!(timing.total_nanos, 0_f64);
+        }
+
+        #[test]
+        fn handles_parse_step() {
+            let timing = Timing::from_reports(
+                day!(1),
+                &[
+                    report(PARSE_PART, PartStatus::Solved, &[1_000]),
+                    report(1, PartStatus::Solved, &[100]),
+                    report(2, PartStatus::Solved, &[10]),
+                ],
+            );
+            assert_eq!(timing.parse.unwrap(), "1.0µs");
+            assert_eq!(timing.parse_stats.unwrap().samples, 1);
+            assert_eq!(timing.part_1.unwrap(), "100.0ns");
+            assert_eq!(timing.total_nanos, 1_110_f64);
+        }
+
+        #[test]
+        fn keeps_memory_of_solved_parts() {
+            let memory = Some(Memory {
+                peak_bytes: 1024,
+                allocations: 2,
+            });
+            let timing = Timing::from_reports(
+                day!(1),
+                &[
+                    PartReport {
+                        memory,
+                        ..report(1, PartStatus::Solved, &[100])
+                    },
+                    PartReport {
+                        memory,
+                        ..report(2, PartStatus::Panicked, &[])
+                    },
+                ],
+            );
+            assert_eq!(timing.part_1_memory, memory);
+            assert_eq!(timing.part_2_memory, None);
+        }
+
+        #[test]
+        fn handles_timed_out_parts() {
+            let timing = Timing::from_reports(
+                day!(1),
+                &[
+                    report(1, PartStatus::Solved, &[100]),
+                    report(2, PartStatus::TimedOut, &[]),
+                ],
+            );
+            assert_eq!(timing.part_1.unwrap(), "100.0ns");
+            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.timed_out, true);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{environment::Environment, fixtures::timing, timings::Timings},
         };
 
         use super::get_mock_timings;
@@ -329,12 +660,8 @@ mod tests {
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![timing(day!(3))],
+                ..Timings::default()
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -349,12 +676,8 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![timing(day!(2))],
+                ..Timings::default()
             };
             let merged = timings.merge(&other);
 
@@ -380,5 +703,47 @@ mod tests {
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 3);
         }
+
+        fn environment(cpu: &str) -> Option<Environment> {
+            Some(Environment {
+                cpu: cpu.into(),
+                cores: 8,
+                governor: None,
+                rustc: "rustc 1.95.0".into(),
+                profile: "release".into(),
+                target_features: vec![],
+                dhat: false,
+            })
+        }
+
+        #[test]
+        fn tracks_mixed_environments() {
+            let stored = Timings {
+                environment: environment("Apple M2"),
+                ..get_mock_timings()
+            };
+            let partial = Timings {
+                data: vec![get_mock_timings().data[0].clone()],
+                environment: environment("Apple M1"),
+                ..Timings::default()
+            };
+
+            let merged = stored.merge(&partial);
+            assert_eq!(merged.environment, environment("Apple M1"));
+            assert_eq!(merged.mixed_environments, true);
+
+            // re-timing every day on one machine clears the warning.
+            let full = Timings {
+                environment: environment("Apple M1"),
+                ..get_mock_timings()
+            };
+            assert_eq!(merged.merge(&full).mixed_environments, false);
+
+            let same_machine = Timings {
+                environment: environment("Apple M2"),
+                ..partial
+            };
+            assert_eq!(stored.merge(&same_machine).mixed_environments, false);
+        }
     }
 }
@@ -0,0 +1,171 @@
+/// Module that backs `watch-solve`, which re-runs a day whenever its sources or data change.
+/// Changes are detected by polling modification times, so that no platform-specific file watcher is needed.
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::template::{
+    Day, Year, bin_path, data_dir, examples,
+    report::{PartReport, PartStatus},
+};
+
+/// The files a day depends on: its module, the shared library, its input and its examples.
+pub fn paths(year: Year, day: Day) -> Vec<PathBuf> {
+    let mut paths = vec![
+        bin_path(year, day),
+        Path::new("src").join("lib.rs"),
+        data_dir(year).join("inputs").join(format!("{day}.txt")),
+    ];
+    paths.extend(examples::paths(year, day));
+    paths
+}
+
+/// Modification times of a set of files, `None` for files that do not exist.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Snapshot(BTreeMap<PathBuf, Option<SystemTime>>);
+
+impl Snapshot {
+    pub fn take(paths: &[PathBuf]) -> Self {
+        Snapshot(
+            paths
+                .iter()
+                .map(|path| {
+                    let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+                    (path.clone(), modified)
+                })
+                .collect(),
+        )
+    }
+
+    /// Files that were created, modified or removed since `self` was taken.
+    pub fn changed<'a>(&'a self, current: &'a Snapshot) -> Vec<&'a Path> {
+        // NOTE: a file that is missing from a snapshot is treated like a file that does not exist.
+        let modified = current
+            .0
+            .iter()
+            .filter(|(path, modified)| self.0.get(*path).copied().flatten() != **modified)
+            .map(|(path, _)| path.as_path());
+
+        let removed = self
+            .0
+            .iter()
+            .filter(|(path, modified)| modified.is_some() && !current.0.contains_key(*path))
+            .map(|(path, _)| path.as_path());
+
+        modified.chain(removed).collect()
+    }
+}
+
+/// What a part produced in a run, e.g. its answer or why it failed.
+fn outcome(reports: &[PartReport], part: u8) -> String {
+    match reports.iter().find(|report| report.part == part) {
+        None => "not run".into(),
+        Some(report) => match (report.status, &report.answer) {
+            (PartStatus::Solved, Some(answer)) => answer.clone(),
+            (PartStatus::Solved | PartStatus::Unsolved, _) => "no result".into(),
+            (PartStatus::Errored, _) => "error".into(),
+            (PartStatus::Panicked, _) => "panicked".into(),
+            (PartStatus::TimedOut, _) => "timed out".into(),
+        },
+    }
+}
+
+/// One line per part that compares the outcome of a run with the previous run, e.g. `Part 1: 41 → 42`.
+pub fn diff_answers(previous: Option<&[PartReport]>, current: &[PartReport]) -> Vec<String> {
+    [1, 2]
+        .into_iter()
+        .map(|part| {
+            let now = outcome(current, part);
+            match previous.map(|previous| outcome(previous, part)) {
+                None => format!("Part {part}: {now}"),
+                Some(before) if before == now => format!("Part {part}: {now} (unchanged)"),
+                Some(before) => format!("Part {part}: {before} → {now}"),
+            }
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        collections::BTreeMap,
+        path::{Path, PathBuf},
+        time::{Duration, SystemTime},
+    };
+
+    use super::{Snapshot, diff_answers};
+    use crate::{
+        day,
+        template::report::{PartReport, PartStatus},
+    };
+
+    fn report(part: u8, status: PartStatus, answer: Option<&str>) -> PartReport {
+        PartReport {
+            day: day!(1),
+            part,
+            status,
+            answer: answer.map(str::to_string),
+            error: None,
+            samples: vec![],
+            memory: None,
+        }
+    }
+
+    #[test]
+    fn detects_changed_files() {
+        let time = SystemTime::UNIX_EPOCH + Duration::from_secs(100);
+        let snapshot = |entries: &[(&str, Option<SystemTime>)]| {
+            Snapshot(
+                entries
+                    .iter()
+                    .map(|(path, modified)| (PathBuf::from(path), *modified))
+                    .collect::<BTreeMap<_, _>>(),
+            )
+        };
+
+        let before = snapshot(&[("a", Some(time)), ("b", None), ("c", Some(time))]);
+        assert_eq!(before.changed(&before.clone()), Vec::<&Path>::new());
+
+        let after = snapshot(&[
+            ("a", Some(time + Duration::from_secs(1))),
+            ("b", Some(time)),
+            ("d", None),
+        ]);
+        assert_eq!(
+            before.changed(&after),
+            vec![Path::new("a"), Path::new("b"), Path::new("c")]
+        );
+    }
+
+    #[test]
+    fn diffs_answers() {
+        let first = vec![
+            report(1, PartStatus::Solved, Some("41")),
+            report(2, PartStatus::Unsolved, None),
+        ];
+        assert_eq!(
+            diff_answers(None, &first),
+            vec!["Part 1: 41", "Part 2: no result"]
+        );
+
+        let second = vec![
+            report(1, PartStatus::Solved, Some("42")),
+            report(2, PartStatus::Unsolved, None),
+        ];
+        assert_eq!(
+            diff_answers(Some(&first), &second),
+            vec!["Part 1: 41 → 42", "Part 2: no result (unchanged)"]
+        );
+
+        let third = vec![report(1, PartStatus::Panicked, None)];
+        assert_eq!(
+            diff_answers(Some(&second), &third),
+            vec!["Part 1: 42 → panicked", "Part 2: no result → not run"]
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::{AllDays, Day};
+
+/// The year of the first Advent of Code event.
+const FIRST_YEAR: u16 = 2015;
+
+/// A valid year of advent (i.e. 2015 or later).
+///
+/// Events up to 2024 have 25 days, starting with 2025 an event only has 12 days.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2024).unwrap();
+/// assert_eq!(year.to_string(), "2024");
+/// assert_eq!(year.last_day().into_inner(), 25);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// The year set by `AOC_YEAR` when the crate was compiled, 2025 if it was not set.
+    pub const DEFAULT: Year = match option_env!("AOC_YEAR") {
+        Some(year) => match Self::parse(year) {
+            Some(year) => year,
+            None => panic!("AOC_YEAR is not set to a valid year"),
+        },
+        None => Year(2025),
+    };
+
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub const fn new(year: u16) -> Option<Self> {
+        if year < FIRST_YEAR {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// The year selected by the `AOC_YEAR` environment variable, [`Year::DEFAULT`] if it's not set.
+    pub fn from_env() -> Result<Self, YearFromStrError> {
+        match env::var("AOC_YEAR") {
+            Ok(year) => year.parse(),
+            Err(_) => Ok(Self::DEFAULT),
+        }
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub const fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The last day of advent in this year.
+    pub const fn last_day(self) -> Day {
+        match Day::new(if self.0 >= 2025 { 12 } else { 25 }) {
+            Some(day) => day,
+            None => unreachable!(),
+        }
+    }
+
+    /// Whether `day` is part of the event of this year.
+    pub fn contains(self, day: Day) -> bool {
+        day <= self.last_day()
+    }
+
+    /// An iterator that yields every day of advent in this year.
+    pub fn days(self) -> AllDays {
+        AllDays::new(self.last_day())
+    }
+
+    const fn parse(s: &str) -> Option<Self> {
+        let bytes = s.as_bytes();
+        if bytes.is_empty() || bytes.len() > 4 {
+            return None;
+        }
+
+        let mut year: u16 = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            if !bytes[i].is_ascii_digit() {
+                return None;
+            }
+            year = year * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+
+        Self::new(year)
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s.trim()).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting a year of advent, i.e. {FIRST_YEAR} or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {
+        const {
+            $crate::template::Year::new($year)
+                .expect("invalid year, expecting a value of 2015 or later")
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+
+    use super::Year;
+    use crate::day;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!(Year::from_str("2024").unwrap(), year!(2024));
+        assert_eq!(Year::from_str("2015").unwrap(), year!(2015));
+        assert_eq!(Year::from_str("2014").is_err(), true);
+        assert_eq!(Year::from_str("24").is_err(), true);
+        assert_eq!(Year::from_str("20x4").is_err(), true);
+        assert_eq!(Year::from_str("").is_err(), true);
+    }
+
+    #[test]
+    fn has_days_per_event() {
+        assert_eq!(year!(2015).last_day(), day!(25));
+        assert_eq!(year!(2024).last_day(), day!(25));
+        assert_eq!(year!(2025).last_day(), day!(12));
+        assert_eq!(year!(2025).contains(day!(12)), true);
+        assert_eq!(year!(2025).contains(day!(13)), false);
+        assert_eq!(year!(2024).days().count(), 25);
+        assert_eq!(year!(2025).days().count(), 12);
+    }
+}